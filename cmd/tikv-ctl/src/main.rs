@@ -301,7 +301,9 @@ trait DebugExecutor {
                     }
                     if cfs.contains(&CF_DEFAULT) {
                         for value_info in mvcc.get_values() {
-                            if commit_ts.map_or(true, |ts| value_info.get_start_ts() == ts) {
+                            // Default CF versions are keyed by the start ts of
+                            // their transaction, they carry no commit ts.
+                            if start_ts.map_or(true, |ts| value_info.get_start_ts() == ts) {
                                 println!("\tdefault cf value: {:?}", value_info);
                             }
                         }
@@ -532,7 +534,7 @@ trait DebugExecutor {
     }
 
     /// Recover the cluster when given `store_ids` are failed.
-    fn remove_fail_stores(&self, store_ids: Vec<u64>, region_ids: Option<Vec<u64>>);
+    fn remove_fail_stores(&self, store_ids: Vec<u64>, region_ids: Option<Vec<u64>>, dry_run: bool);
 
     /// Recreate the region with metadata from pd, but alloc new id for it.
     fn recreate_region(&self, sec_mgr: Arc<SecurityManager>, pd_cfg: &PdConfig, region_id: u64);
@@ -760,7 +762,7 @@ impl DebugExecutor for DebugClient {
         unimplemented!("only available for local mode");
     }
 
-    fn remove_fail_stores(&self, _: Vec<u64>, _: Option<Vec<u64>>) {
+    fn remove_fail_stores(&self, _: Vec<u64>, _: Option<Vec<u64>>, _: bool) {
         self.check_local_mode();
     }
 
@@ -938,9 +940,16 @@ impl<ER: RaftEngine> DebugExecutor for Debugger<ER> {
         println!("all regions are healthy")
     }
 
-    fn remove_fail_stores(&self, store_ids: Vec<u64>, region_ids: Option<Vec<u64>>) {
-        println!("removing stores {:?} from configurations...", store_ids);
-        self.remove_failed_stores(store_ids, region_ids)
+    fn remove_fail_stores(&self, store_ids: Vec<u64>, region_ids: Option<Vec<u64>>, dry_run: bool) {
+        if dry_run {
+            println!(
+                "planning removal of stores {:?} from configurations, nothing will be written...",
+                store_ids
+            );
+        } else {
+            println!("removing stores {:?} from configurations...", store_ids);
+        }
+        self.remove_failed_stores(store_ids, region_ids, dry_run)
             .unwrap_or_else(|e| perror_and_exit("Debugger::remove_fail_stores", e));
         println!("success");
     }
@@ -1189,6 +1198,14 @@ fn main() {
                                 .takes_value(true)
                                 .help("Set the raft log index"),
                         )
+                        .arg(
+                            Arg::with_name("end-index")
+                                .requires("index")
+                                .conflicts_with("key")
+                                .long("end-index")
+                                .takes_value(true)
+                                .help("Dump all entries in the index range [index, end-index)"),
+                        )
                         .arg(
                             Arg::with_name("key")
                                 .required_unless_one(&["region", "index"])
@@ -1596,6 +1613,12 @@ fn main() {
                                 .takes_value(false)
                                 .help("Do the command for all regions"),
                         )
+                        .arg(
+                            Arg::with_name("dry-run")
+                                .long("dry-run")
+                                .takes_value(false)
+                                .help("Only log the peer list changes that would be made"),
+                        )
                 ),
         )
         .subcommand(
@@ -2101,7 +2124,20 @@ fn main() {
                 let index = matches.value_of("index").unwrap().parse().unwrap();
                 (id, index)
             };
-            debug_executor.dump_raft_log(id, index);
+            match matches.value_of("end-index") {
+                Some(end) => {
+                    let end_index: u64 = end.parse().unwrap();
+                    if end_index <= index {
+                        println!("end-index must be greater than index");
+                        process::exit(-1);
+                    }
+                    for i in index..end_index {
+                        debug_executor.dump_raft_log(id, i);
+                        println!();
+                    }
+                }
+                None => debug_executor.dump_raft_log(id, index),
+            }
         } else if let Some(matches) = matches.subcommand_matches("region") {
             let skip_tombstone = matches.is_present("skip-tombstone");
             if let Some(id) = matches.value_of("region") {
@@ -2246,7 +2282,7 @@ fn main() {
                     .collect::<Result<Vec<_>, _>>()
                     .expect("parse regions fail")
             });
-            debug_executor.remove_fail_stores(store_ids, region_ids);
+            debug_executor.remove_fail_stores(store_ids, region_ids, matches.is_present("dry-run"));
         } else {
             println!("{}", matches.usage());
         }
@@ -2475,6 +2511,7 @@ fn compact_whole_cluster(
         let addr = s.address.clone();
         let (from, to) = (from.clone(), to.clone());
         let cfs: Vec<String> = cfs.iter().map(|cf| cf.to_string()).collect();
+        println!("compacting store:{}", addr);
         let h = thread::Builder::new()
             .name(format!("compact-{}", addr))
             .spawn(move || {
@@ -2494,11 +2531,22 @@ fn compact_whole_cluster(
                 tikv_alloc::remove_thread_memory_accessor();
             })
             .unwrap();
-        handles.push(h);
+        handles.push((s.address, h));
     }
 
-    for h in handles {
-        h.join().unwrap();
+    // Wait for all stores so that one failed store doesn't hide the progress
+    // of the others, then report the failures in one place.
+    let mut failed_stores = Vec::new();
+    for (addr, h) in handles {
+        if h.join().is_err() {
+            failed_stores.push(addr);
+        }
+    }
+    if failed_stores.is_empty() {
+        println!("compact whole cluster success!");
+    } else {
+        eprintln!("compact failed on stores: {}", failed_stores.join(", "));
+        process::exit(-1);
     }
 }
 
@@ -2646,7 +2694,13 @@ fn print_bad_ssts(db: &str, manifest: Option<&str>, pd_client: RpcClient, cfg: &
             let end = from_hex(matches.get(2).unwrap().as_str()).unwrap();
 
             if start.starts_with(&[keys::DATA_PREFIX]) {
-                print_overlap_region_and_suggestions(&pd_client, &start[1..], &end[1..], db, path);
+                print_overlap_region_and_suggestions(
+                    &pd_client,
+                    &start[1..],
+                    &end[1..],
+                    db,
+                    sst_file_number,
+                );
             } else if start.starts_with(&[keys::LOCAL_PREFIX]) {
                 println!(
                     "it isn't easy to handle local data, start key:{}",
@@ -2655,7 +2709,13 @@ fn print_bad_ssts(db: &str, manifest: Option<&str>, pd_client: RpcClient, cfg: &
 
                 // consider the case that include both meta and user data
                 if end.starts_with(&[keys::DATA_PREFIX]) {
-                    print_overlap_region_and_suggestions(&pd_client, &[], &end[1..], db, path);
+                    print_overlap_region_and_suggestions(
+                        &pd_client,
+                        &[],
+                        &end[1..],
+                        db,
+                        sst_file_number,
+                    );
                 }
             } else {
                 println!("unexpected key {}", log_wrappers::Value(&start));
@@ -2677,7 +2737,7 @@ fn print_overlap_region_and_suggestions(
     start: &[u8],
     end: &[u8],
     db: &str,
-    sst_path: &Path,
+    sst_file_number: &str,
 ) {
     let mut key = start.to_vec();
     let mut regions_to_print = vec![];
@@ -2703,9 +2763,10 @@ fn print_overlap_region_and_suggestions(
     }
 
     println!("\nsuggested operations:");
+    // `unsafe_remove_sst_file` takes the file number, not the path.
     println!(
-        "tikv-ctl ldb --db={} unsafe_remove_sst_file {:?}",
-        db, sst_path
+        "tikv-ctl ldb --db={} unsafe_remove_sst_file {}",
+        db, sst_file_number
     );
     for region in regions_to_print {
         println!(
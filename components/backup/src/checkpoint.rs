@@ -0,0 +1,170 @@
+// Copyright 2021 TiKV Project Authors. Licensed under Apache-2.0.
+
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::sync::Mutex;
+
+use external_storage_export::ExternalStorage;
+use futures_util::io::{AsyncReadExt, Cursor};
+use tikv_util::stream::block_on_external_io;
+use tikv_util::{info, warn};
+
+/// Region-level checkpointing of a backup task.
+///
+/// Completed ranges are reported out of order by the backup workers. The
+/// checkpoint tracks the largest key below which every range has been fully
+/// saved, and persists it to the external storage, so a backup interrupted by
+/// a node restart can be resumed from the persisted watermark instead of
+/// starting over.
+///
+/// All keys are in the encoded (memcomparable) form that `Progress` seeks
+/// regions with.
+pub struct Checkpoint {
+    // The file name is derived from the task, so only the matching task can
+    // resume from it.
+    name: String,
+    inner: Mutex<CheckpointInner>,
+}
+
+struct CheckpointInner {
+    // The contiguously completed prefix of the task range ends here.
+    watermark: Vec<u8>,
+    // Completed ranges above the watermark, keyed by their start key.
+    completed: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl Checkpoint {
+    /// Creates an in-memory checkpoint that has no persisted state yet. The
+    /// persisted file name is derived from the task parameters alone, so a
+    /// checkpoint created without storage access still persists to the name
+    /// `load` will read on resume.
+    pub fn new(store_id: u64, start_key: Vec<u8>, end_key: &[u8], backup_ts: u64) -> Checkpoint {
+        let mut input = start_key.clone();
+        input.extend_from_slice(end_key);
+        input.extend_from_slice(&backup_ts.to_be_bytes());
+        let hash = file_system::sha256(&input)
+            .map(hex::encode)
+            .unwrap_or_default();
+        Checkpoint {
+            name: format!("checkpoint_{}_{}", store_id, hash),
+            inner: Mutex::new(CheckpointInner {
+                watermark: start_key,
+                completed: BTreeMap::new(),
+            }),
+        }
+    }
+
+    /// Creates the checkpoint of a backup task that starts at `start_key` and
+    /// tries to load a previously persisted watermark from the storage.
+    /// Returns the checkpoint and the resume point, if any.
+    pub fn load(
+        store_id: u64,
+        start_key: Vec<u8>,
+        end_key: &[u8],
+        backup_ts: u64,
+        storage: &dyn ExternalStorage,
+    ) -> (Checkpoint, Option<Vec<u8>>) {
+        let mut checkpoint = Checkpoint::new(store_id, start_key.clone(), end_key, backup_ts);
+
+        let resume = match Self::read_watermark(&checkpoint.name, storage) {
+            Ok(Some(watermark)) if watermark > start_key => {
+                info!("backup resumes from checkpoint";
+                    "name" => &checkpoint.name,
+                    "watermark" => &log_wrappers::Value::key(&watermark),
+                );
+                Some(watermark)
+            }
+            Ok(_) => None,
+            Err(e) => {
+                warn!("backup failed to read checkpoint, start over"; "err" => ?e);
+                None
+            }
+        };
+        if let Some(watermark) = &resume {
+            checkpoint.inner.get_mut().unwrap().watermark = watermark.clone();
+        }
+        (checkpoint, resume)
+    }
+
+    fn read_watermark(
+        name: &str,
+        storage: &dyn ExternalStorage,
+    ) -> std::io::Result<Option<Vec<u8>>> {
+        let mut reader = storage.read(name);
+        let mut watermark = Vec::new();
+        match block_on_external_io(reader.read_to_end(&mut watermark)) {
+            Ok(_) => Ok(Some(watermark)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Marks `[start_key, end_key)` as completed and persists the new
+    /// watermark when the contiguously completed prefix advances. An empty
+    /// `end_key` means the range is unbounded on the right.
+    ///
+    /// Persisting is best effort: a failure only degrades a future resume,
+    /// so it is logged instead of failing the backup.
+    pub fn complete(&self, start_key: Vec<u8>, end_key: Vec<u8>, storage: &dyn ExternalStorage) {
+        let watermark = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.completed.insert(start_key, end_key);
+            let mut advanced = false;
+            loop {
+                let next = inner.watermark.clone();
+                match inner.completed.remove(&next) {
+                    Some(end) => {
+                        advanced = true;
+                        let finished = end.is_empty();
+                        inner.watermark = end;
+                        if finished {
+                            break;
+                        }
+                    }
+                    None => break,
+                }
+            }
+            if !advanced {
+                return;
+            }
+            inner.watermark.clone()
+        };
+        let res = storage.write(
+            &self.name,
+            Box::new(Cursor::new(watermark.clone())),
+            watermark.len() as u64,
+        );
+        if let Err(e) = res {
+            warn!("backup failed to persist checkpoint"; "err" => ?e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_watermark() {
+        let temp = tempfile::tempdir().unwrap();
+        let backend = external_storage_export::make_local_backend(temp.path());
+        let storage = external_storage_export::create_storage(&backend).unwrap();
+
+        let (checkpoint, resume) = Checkpoint::load(1, vec![], b"z", 10, &storage);
+        assert!(resume.is_none());
+
+        // Completing an out of order range does not advance the watermark.
+        checkpoint.complete(b"b".to_vec(), b"c".to_vec(), &storage);
+        let (_, resume) = Checkpoint::load(1, vec![], b"z", 10, &storage);
+        assert!(resume.is_none());
+
+        // Completing the first range advances through both.
+        checkpoint.complete(b"".to_vec(), b"b".to_vec(), &storage);
+        let (_, resume) = Checkpoint::load(1, vec![], b"z", 10, &storage);
+        assert_eq!(resume.unwrap(), b"c".to_vec());
+
+        // A different task must not pick up this checkpoint.
+        let (_, resume) = Checkpoint::load(1, vec![], b"z", 11, &storage);
+        assert!(resume.is_none());
+    }
+}
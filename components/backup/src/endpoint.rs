@@ -37,6 +37,7 @@ use txn_types::{Key, Lock, TimeStamp};
 use yatp::task::callback::{Handle, TaskCell};
 use yatp::ThreadPool;
 
+use crate::checkpoint::Checkpoint;
 use crate::metrics::*;
 use crate::writer::BackupWriterBuilder;
 use crate::Error;
@@ -106,6 +107,14 @@ impl Task {
         let cf = name_to_cf(req.get_cf()).ok_or_else(|| crate::Error::InvalidCf {
             cf: req.get_cf().to_owned(),
         })?;
+        // An incremental backup scans commit ts in (start_version, end_version],
+        // so the range must not be reversed.
+        if req.get_start_version() > req.get_end_version() {
+            return Err(crate::Error::InvalidTsRange {
+                start_ts: req.get_start_version(),
+                end_ts: req.get_end_version(),
+            });
+        }
 
         let task = Task {
             request: Request {
@@ -381,6 +390,7 @@ impl BackupRange {
         cf: CfName,
         compression_type: Option<SstCompressionType>,
         compression_level: i32,
+        encryption_key: Option<Arc<Vec<u8>>>,
     ) -> Result<(Vec<File>, Statistics)> {
         let mut writer = match BackupRawKVWriter::new(
             db,
@@ -389,6 +399,7 @@ impl BackupRange {
             storage.limiter.clone(),
             compression_type,
             compression_level,
+            encryption_key,
         ) {
             Ok(w) => w,
             Err(e) => {
@@ -624,6 +635,7 @@ impl<E: Engine, R: RegionInfoProvider + Clone + 'static> Endpoint<E, R> {
         &self,
         prs: Arc<Mutex<Progress<R>>>,
         request: Request,
+        checkpoint: Arc<Checkpoint>,
         tx: UnboundedSender<BackupResponse>,
     ) {
         let start_ts = request.start_ts;
@@ -635,6 +647,13 @@ impl<E: Engine, R: RegionInfoProvider + Clone + 'static> Endpoint<E, R> {
         let concurrency_manager = self.concurrency_manager.clone();
         let batch_size = self.config_manager.0.read().unwrap().batch_size;
         let sst_max_size = self.config_manager.0.read().unwrap().sst_max_size.0;
+        let encryption_key_file = self
+            .config_manager
+            .0
+            .read()
+            .unwrap()
+            .sst_encryption_key_file
+            .clone();
 
         // TODO: make it async.
         self.pool.borrow_mut().spawn(move || {
@@ -658,6 +677,27 @@ impl<E: Engine, R: RegionInfoProvider + Clone + 'static> Endpoint<E, R> {
                 }
             };
 
+            // Load the user provided key used to encrypt backup SST files.
+            let encryption_key = if encryption_key_file.is_empty() {
+                None
+            } else {
+                match tikv::config::load_sst_encryption_key(&encryption_key_file) {
+                    Ok(key) => Some(Arc::new(key)),
+                    Err(err) => {
+                        error_unknown!(?err; "backup load sst encryption key failed");
+                        let mut response = BackupResponse::default();
+                        response.set_error(
+                            crate::Error::Other(box_err!("load sst encryption key: {}", err))
+                                .into(),
+                        );
+                        if let Err(err) = tx.unbounded_send(response) {
+                            error_unknown!(?err; "backup failed to send response");
+                        }
+                        return;
+                    }
+                }
+            };
+
             let storage = LimitedStorage {
                 limiter: request.limiter,
                 storage: Arc::new(backend),
@@ -681,6 +721,14 @@ impl<E: Engine, R: RegionInfoProvider + Clone + 'static> Endpoint<E, R> {
                         warn!("backup task has canceled"; "range" => ?brange);
                         return;
                     }
+                    let ck_start = brange
+                        .start_key
+                        .clone()
+                        .map_or_else(Vec::new, |k| k.into_encoded());
+                    let ck_end = brange
+                        .end_key
+                        .clone()
+                        .map_or_else(Vec::new, |k| k.into_encoded());
                     // TODO: make file_name unique and short
                     let key = brange.start_key.clone().and_then(|k| {
                         // use start_key sha256 instead of start_key to avoid file name too long os error
@@ -704,6 +752,7 @@ impl<E: Engine, R: RegionInfoProvider + Clone + 'static> Endpoint<E, R> {
                                 cf,
                                 ct,
                                 request.compression_level,
+                                encryption_key.clone(),
                             ),
                             brange.start_key.map_or_else(Vec::new, |k| k.into_encoded()),
                             brange.end_key.map_or_else(Vec::new, |k| k.into_encoded()),
@@ -717,6 +766,7 @@ impl<E: Engine, R: RegionInfoProvider + Clone + 'static> Endpoint<E, R> {
                             ct,
                             request.compression_level,
                             sst_max_size,
+                            encryption_key.clone(),
                         );
                         (
                             brange.backup(
@@ -762,6 +812,9 @@ impl<E: Engine, R: RegionInfoProvider + Clone + 'static> Endpoint<E, R> {
                                 file.set_end_version(end_ts.into_inner());
                             }
                             response.set_files(files.into());
+                            // The range is fully saved, move the checkpoint
+                            // forward so a restarted backup can skip it.
+                            checkpoint.complete(ck_start, ck_end, storage.storage.as_ref());
                         }
                     }
                     response.set_start_key(start_key);
@@ -797,6 +850,37 @@ impl<E: Engine, R: RegionInfoProvider + Clone + 'static> Endpoint<E, R> {
             Some(Key::from_raw(&request.end_key))
         };
 
+        // Try to resume from a previously persisted checkpoint of the same
+        // task. Failing to open the storage here is not fatal, the workers
+        // will report it when they open it themselves.
+        let encoded_start = start_key
+            .clone()
+            .map_or_else(Vec::new, |k| k.into_encoded());
+        let encoded_end = end_key.clone().map_or_else(Vec::new, |k| k.into_encoded());
+        let (checkpoint, resume) = match create_storage(&request.backend) {
+            Ok(storage) => Checkpoint::load(
+                self.store_id,
+                encoded_start,
+                &encoded_end,
+                request.end_ts.into_inner(),
+                &storage,
+            ),
+            Err(err) => {
+                warn!("backup failed to open storage for checkpoint"; "err" => ?err);
+                (
+                    Checkpoint::new(
+                        self.store_id,
+                        encoded_start,
+                        &encoded_end,
+                        request.end_ts.into_inner(),
+                    ),
+                    None,
+                )
+            }
+        };
+        let start_key = resume.map(Key::from_encoded).or(start_key);
+        let checkpoint = Arc::new(checkpoint);
+
         let prs = Arc::new(Mutex::new(Progress::new(
             self.store_id,
             start_key,
@@ -808,7 +892,7 @@ impl<E: Engine, R: RegionInfoProvider + Clone + 'static> Endpoint<E, R> {
         let concurrency = self.config_manager.0.read().unwrap().num_threads;
         self.pool.borrow_mut().adjust_with(concurrency);
         for _ in 0..concurrency {
-            self.spawn_backup_worker(prs.clone(), request.clone(), resp.clone());
+            self.spawn_backup_worker(prs.clone(), request.clone(), checkpoint.clone(), resp.clone());
         }
     }
 }
@@ -921,7 +1005,7 @@ pub mod tests {
     use raftstore::store::util::new_peer;
     use rand::Rng;
     use tempfile::TempDir;
-    use tikv::storage::txn::tests::{must_commit, must_prewrite_put};
+    use tikv::storage::txn::tests::{must_commit, must_prewrite_delete, must_prewrite_put};
     use tikv::storage::{RocksEngine, TestEngineBuilder};
     use tikv_util::config::ReadableSize;
     use txn_types::SHORT_VALUE_MAX_LEN;
@@ -1438,6 +1522,78 @@ pub mod tests {
         });
     }
 
+    #[test]
+    fn test_invalid_ts_range() {
+        let mut req = BackupRequest::default();
+        req.set_start_key(vec![]);
+        req.set_end_key(vec![]);
+        req.set_start_version(2);
+        req.set_end_version(1);
+        req.set_storage_backend(make_noop_backend());
+
+        let (tx, _) = unbounded();
+        let err = Task::new(req, tx).unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidTsRange { start_ts: 2, end_ts: 1 }),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_incremental_backup() {
+        let (tmp, endpoint) = new_endpoint();
+        let engine = endpoint.engine.clone();
+
+        endpoint
+            .region_info
+            .set_regions(vec![(b"".to_vec(), b"5".to_vec(), 1)]);
+
+        let mut ts = TimeStamp::new(1);
+        let mut alloc_ts = || *ts.incr();
+        // Commit key 1 before the last backup.
+        let (start, commit) = (alloc_ts(), alloc_ts());
+        must_prewrite_put(&engine, b"1", b"v1", b"1", start);
+        must_commit(&engine, b"1", start, commit);
+        let last_backup_ts = alloc_ts();
+        // Commit key 2 and delete key 1 after the last backup. Both mutations
+        // must be included in the incremental backup.
+        let (start, commit) = (alloc_ts(), alloc_ts());
+        must_prewrite_put(&engine, b"2", b"v2", b"2", start);
+        must_commit(&engine, b"2", start, commit);
+        let (start, commit) = (alloc_ts(), alloc_ts());
+        must_prewrite_delete(&engine, b"1", b"1", start);
+        must_commit(&engine, b"1", start, commit);
+        let backup_ts = alloc_ts();
+
+        let mut req = BackupRequest::default();
+        req.set_start_key(vec![]);
+        req.set_end_key(vec![b'5']);
+        req.set_start_version(last_backup_ts.into_inner());
+        req.set_end_version(backup_ts.into_inner());
+        let tmp1 = make_unique_dir(tmp.path());
+        req.set_storage_backend(make_local_backend(&tmp1));
+
+        let (tx, rx) = unbounded();
+        let (task, _) = Task::new(req, tx).unwrap();
+        endpoint.handle_backup_task(task);
+        check_response(rx, |resp| {
+            let resp = resp.unwrap();
+            assert!(!resp.has_error(), "{:?}", resp);
+            let files = resp.get_files();
+            assert!(!files.is_empty(), "{:?}", resp);
+            for file in files {
+                assert_eq!(file.get_start_version(), last_backup_ts.into_inner());
+                assert_eq!(file.get_end_version(), backup_ts.into_inner());
+            }
+            // The put of key 2 and the delete of key 1 are both emitted. Both
+            // values are short enough to be inlined into the write CF, so no
+            // default CF entries are counted.
+            let total_kvs: u64 = files.iter().map(|f| f.get_total_kvs()).sum();
+            assert_eq!(total_kvs, 2, "{:?}", resp);
+        });
+    }
+
     #[test]
     fn test_adjust_thread_pool_size() {
         let (_tmp, endpoint) = new_endpoint();
@@ -113,6 +113,8 @@ pub enum Error {
     ClusterID { current: u64, request: u64 },
     #[error("Invalid cf {cf}")]
     InvalidCf { cf: String },
+    #[error("Invalid ts range, start_ts {start_ts} > end_ts {end_ts}")]
+    InvalidTsRange { start_ts: u64, end_ts: u64 },
 }
 
 macro_rules! impl_from {
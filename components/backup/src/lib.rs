@@ -5,6 +5,7 @@
 #[allow(unused_extern_crates)]
 extern crate tikv_alloc;
 
+mod checkpoint;
 mod endpoint;
 mod errors;
 mod metrics;
@@ -1,7 +1,9 @@
 // Copyright 2019 TiKV Project Authors. Licensed under Apache-2.0.
 
+use std::io::Read;
 use std::sync::Arc;
 
+use encryption::EncrypterReader;
 use engine_rocks::raw::DB;
 use engine_rocks::{RocksEngine, RocksSstWriter, RocksSstWriterBuilder};
 use engine_traits::{CfName, CF_DEFAULT, CF_WRITE};
@@ -10,6 +12,7 @@ use external_storage_export::ExternalStorage;
 use file_system::Sha256Reader;
 use futures_util::io::AllowStdIo;
 use kvproto::brpb::File;
+use kvproto::encryptionpb::EncryptionMethod;
 use kvproto::metapb::Region;
 use tikv::coprocessor::checksum_crc64_xor;
 use tikv::storage::txn::TxnEntry;
@@ -76,6 +79,7 @@ impl Writer {
         name: &str,
         cf: &'static str,
         limiter: Limiter,
+        encryption_key: Option<&[u8]>,
         storage: &dyn ExternalStorage,
     ) -> Result<File> {
         let (sst_info, sst_reader) = self.writer.finish_read()?;
@@ -84,12 +88,26 @@ impl Writer {
             .observe(sst_info.file_size() as f64);
         let file_name = format!("{}_{}.sst", name, cf);
 
-        let (reader, hasher) = Sha256Reader::new(sst_reader)
+        // When encryption is enabled, the random IV is stored in clear at the
+        // head of the file, so the restore side only needs the shared key to
+        // decrypt. The sha256 covers the bytes as stored, IV included.
+        let (content, content_size): (Box<dyn Read + Send>, u64) = match encryption_key {
+            Some(key) => {
+                let (encrypted, iv) =
+                    EncrypterReader::new(sst_reader, EncryptionMethod::Aes256Ctr, key)
+                        .map_err(|e| Error::Other(box_err!("Encrypt error: {:?}", e)))?;
+                let iv = iv.as_slice().to_vec();
+                let size = sst_info.file_size() + iv.len() as u64;
+                (Box::new(std::io::Cursor::new(iv).chain(encrypted)), size)
+            }
+            None => (Box::new(sst_reader), sst_info.file_size()),
+        };
+        let (reader, hasher) = Sha256Reader::new(content)
             .map_err(|e| Error::Other(box_err!("Sha256 error: {:?}", e)))?;
         storage.write(
             &file_name,
             Box::new(limiter.limit(AllowStdIo::new(reader))),
-            sst_info.file_size(),
+            content_size,
         )?;
         let sha256 = hasher
             .lock()
@@ -105,7 +123,7 @@ impl Writer {
         file.set_total_kvs(self.total_kvs);
         file.set_total_bytes(self.total_bytes);
         file.set_cf(cf.to_owned());
-        file.set_size(sst_info.file_size());
+        file.set_size(content_size);
         Ok(file)
     }
 
@@ -122,6 +140,7 @@ pub struct BackupWriterBuilder {
     compression_type: Option<SstCompressionType>,
     compression_level: i32,
     sst_max_size: u64,
+    encryption_key: Option<Arc<Vec<u8>>>,
 }
 
 impl BackupWriterBuilder {
@@ -133,6 +152,7 @@ impl BackupWriterBuilder {
         compression_type: Option<SstCompressionType>,
         compression_level: i32,
         sst_max_size: u64,
+        encryption_key: Option<Arc<Vec<u8>>>,
     ) -> BackupWriterBuilder {
         Self {
             store_id,
@@ -142,6 +162,7 @@ impl BackupWriterBuilder {
             compression_type,
             compression_level,
             sst_max_size,
+            encryption_key,
         }
     }
 
@@ -156,6 +177,7 @@ impl BackupWriterBuilder {
             self.compression_level,
             self.limiter.clone(),
             self.sst_max_size,
+            self.encryption_key.clone(),
         )
     }
 }
@@ -167,6 +189,7 @@ pub struct BackupWriter {
     write: Writer,
     limiter: Limiter,
     sst_max_size: u64,
+    encryption_key: Option<Arc<Vec<u8>>>,
 }
 
 impl BackupWriter {
@@ -178,6 +201,7 @@ impl BackupWriter {
         compression_level: i32,
         limiter: Limiter,
         sst_max_size: u64,
+        encryption_key: Option<Arc<Vec<u8>>>,
     ) -> Result<BackupWriter> {
         let default = RocksSstWriterBuilder::new()
             .set_in_memory(true)
@@ -200,6 +224,7 @@ impl BackupWriter {
             write: Writer::new(write),
             limiter,
             sst_max_size,
+            encryption_key,
         })
     }
 
@@ -238,12 +263,14 @@ impl BackupWriter {
         let start = Instant::now();
         let mut files = Vec::with_capacity(2);
         let write_written = !self.write.is_empty() || !self.default.is_empty();
+        let encryption_key = self.encryption_key.as_ref().map(|k| k.as_slice());
         if !self.default.is_empty() {
             // Save default cf contents.
             let default = self.default.save_and_build_file(
                 &self.name,
                 CF_DEFAULT,
                 self.limiter.clone(),
+                encryption_key,
                 storage,
             )?;
             files.push(default);
@@ -254,6 +281,7 @@ impl BackupWriter {
                 &self.name,
                 CF_WRITE,
                 self.limiter.clone(),
+                encryption_key,
                 storage,
             )?;
             files.push(write);
@@ -279,6 +307,7 @@ pub struct BackupRawKVWriter {
     cf: CfName,
     writer: Writer,
     limiter: Limiter,
+    encryption_key: Option<Arc<Vec<u8>>>,
 }
 
 impl BackupRawKVWriter {
@@ -290,6 +319,7 @@ impl BackupRawKVWriter {
         limiter: Limiter,
         compression_type: Option<SstCompressionType>,
         compression_level: i32,
+        encryption_key: Option<Arc<Vec<u8>>>,
     ) -> Result<BackupRawKVWriter> {
         let writer = RocksSstWriterBuilder::new()
             .set_in_memory(true)
@@ -303,6 +333,7 @@ impl BackupRawKVWriter {
             cf,
             writer: Writer::new(writer),
             limiter,
+            encryption_key,
         })
     }
 
@@ -336,6 +367,7 @@ impl BackupRawKVWriter {
                 &self.name,
                 self.cf,
                 self.limiter.clone(),
+                self.encryption_key.as_ref().map(|k| k.as_slice()),
                 storage,
             )?;
             files.push(file);
@@ -423,6 +455,7 @@ mod tests {
             0,
             Limiter::new(INFINITY),
             144 * 1024 * 1024,
+            None,
         )
         .unwrap();
         writer.write(vec![].into_iter(), false).unwrap();
@@ -436,6 +469,7 @@ mod tests {
             0,
             Limiter::new(INFINITY),
             144 * 1024 * 1024,
+            None,
         )
         .unwrap();
         writer
@@ -470,6 +504,7 @@ mod tests {
             0,
             Limiter::new(INFINITY),
             144 * 1024 * 1024,
+            None,
         )
         .unwrap();
         writer
@@ -518,4 +553,68 @@ mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_encrypted_writer() {
+        use encryption::{DecrypterReader, Iv};
+
+        let temp = TempDir::new().unwrap();
+        let rocks = TestEngineBuilder::new()
+            .path(temp.path())
+            .cfs(&[engine_traits::CF_DEFAULT, engine_traits::CF_WRITE])
+            .build()
+            .unwrap();
+        let db = rocks.get_rocksdb();
+        let backend = external_storage_export::make_local_backend(temp.path());
+        let storage = external_storage_export::create_storage(&backend).unwrap();
+
+        let key = Arc::new(vec![7u8; 32]);
+        let mut writer = BackupWriter::new(
+            db.get_sync_db(),
+            "foo_enc",
+            None,
+            0,
+            Limiter::new(INFINITY),
+            144 * 1024 * 1024,
+            Some(key.clone()),
+        )
+        .unwrap();
+        writer
+            .write(
+                vec![TxnEntry::Commit {
+                    default: (vec![], vec![]),
+                    write: (vec![b'a'], vec![b'a']),
+                    old_value: None,
+                }]
+                .into_iter(),
+                false,
+            )
+            .unwrap();
+        let files = writer.save(&storage).unwrap();
+        assert_eq!(files.len(), 1);
+
+        // The stored file is IV + ciphertext. Decrypt it back into a plain
+        // SST and verify its content.
+        let stored = std::fs::read(temp.path().join(files[0].get_name())).unwrap();
+        assert_eq!(stored.len() as u64, files[0].get_size());
+        let (iv, ciphertext) = stored.split_at(16);
+        let mut decrypter = DecrypterReader::new(
+            std::io::Cursor::new(ciphertext),
+            EncryptionMethod::Aes256Ctr,
+            &key,
+            Iv::from_slice(iv).unwrap(),
+        )
+        .unwrap();
+        let mut plaintext = Vec::new();
+        decrypter.read_to_end(&mut plaintext).unwrap();
+        let sst_path = temp.path().join("decrypted.sst");
+        std::fs::write(&sst_path, &plaintext).unwrap();
+        check_sst(
+            &[(engine_traits::CF_WRITE, sst_path.as_path())],
+            &[(
+                engine_traits::CF_WRITE,
+                &[(&keys::data_key(&[b'a']), &[b'a'])],
+            )],
+        );
+    }
 }
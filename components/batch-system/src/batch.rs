@@ -8,9 +8,11 @@
 use crate::config::Config;
 use crate::fsm::{Fsm, FsmScheduler, Priority};
 use crate::mailbox::BasicMailbox;
+use crate::metrics::*;
 use crate::router::Router;
 use crossbeam::channel::{self, SendError};
 use file_system::{set_io_type, IOType};
+use prometheus::{Histogram, IntCounter};
 use std::borrow::Cow;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
@@ -253,6 +255,8 @@ struct Poller<N: Fsm, C: Fsm, Handler> {
     handler: Handler,
     max_batch_size: usize,
     reschedule_duration: Duration,
+    reschedule_counter: IntCounter,
+    fsm_count_per_round: Histogram,
 }
 
 enum ReschedulePolicy {
@@ -319,6 +323,7 @@ impl<N: Fsm, C: Fsm, Handler: PollHandler<N, C>> Poller<N, C, Handler> {
                         // it's possible all the hot regions are fetched in a batch the
                         // next time.
                         if hot_fsm_count % 2 == 0 {
+                            self.reschedule_counter.inc();
                             reschedule_fsms.push((i, ReschedulePolicy::Schedule));
                             continue;
                         }
@@ -348,6 +353,7 @@ impl<N: Fsm, C: Fsm, Handler: PollHandler<N, C>> Poller<N, C, Handler> {
                 fsm_cnt += 1;
             }
             self.handler.end(&mut batch.normals);
+            self.fsm_count_per_round.observe(batch.normals.len() as f64);
 
             // Because release use `swap_remove` internally, so using pop here
             // to remove the correct FSM.
@@ -406,12 +412,17 @@ where
             Priority::Normal => self.receiver.clone(),
             Priority::Low => self.low_receiver.clone(),
         };
+        // All pollers of one batch system share the same metrics, so label
+        // them by the name prefix rather than the thread name.
+        let name_prefix = self.name_prefix.as_deref().unwrap();
         let mut poller = Poller {
             router: self.router.clone(),
             fsm_receiver: receiver,
             handler,
             max_batch_size: self.max_batch_size,
             reschedule_duration: self.reschedule_duration,
+            reschedule_counter: FSM_RESCHEDULE_COUNTER_VEC.with_label_values(&[name_prefix]),
+            fsm_count_per_round: FSM_COUNT_PER_POLL_ROUND_VEC.with_label_values(&[name_prefix]),
         };
         let props = tikv_util::thread_group::current_properties();
         let t = thread::Builder::new()
@@ -431,6 +442,7 @@ where
         B: HandlerBuilder<N, C>,
         B::Handler: Send + 'static,
     {
+        self.name_prefix = Some(name_prefix.clone());
         for i in 0..self.pool_size {
             self.start_poller(
                 thd_name!(format!("{}-{}", name_prefix, i)),
@@ -445,7 +457,6 @@ where
                 &mut builder,
             );
         }
-        self.name_prefix = Some(name_prefix);
     }
 
     /// Shutdown the batch system and wait till all background threads exit.
@@ -0,0 +1,20 @@
+// Copyright 2021 TiKV Project Authors. Licensed under Apache-2.0.
+
+use lazy_static::lazy_static;
+use prometheus::*;
+
+lazy_static! {
+    pub static ref FSM_RESCHEDULE_COUNTER_VEC: IntCounterVec = register_int_counter_vec!(
+        "tikv_batch_system_fsm_reschedule_total",
+        "Total number of fsms rescheduled to the back of the poll queue.",
+        &["name"]
+    )
+    .unwrap();
+    pub static ref FSM_COUNT_PER_POLL_ROUND_VEC: HistogramVec = register_histogram_vec!(
+        "tikv_batch_system_fsm_count_per_poll_round",
+        "Bucketed histogram of the number of fsms handled in one poll round.",
+        &["name"],
+        exponential_buckets(1.0, 2.0, 10).unwrap()
+    )
+    .unwrap();
+}
@@ -25,6 +25,15 @@ const CDC_MSG_MAX_BATCH_SIZE: usize = 128;
 // 2 = (CDC_MSG_MAX_BATCH_SIZE * 1KB / service::CDC_MAX_RESP_SIZE).ceil() + 1 /* reserve for ResolvedTs */;
 pub const CDC_EVENT_MAX_BATCH_SIZE: usize = 2;
 
+// How long a batched send is willing to wait for the memory quota before
+// reporting congestion. Incremental scans can tolerate back-pressure, so
+// pause them for a while instead of aborting immediately.
+#[cfg(not(test))]
+const QUOTA_WAIT_TIMEOUT: Duration = Duration::from_secs(1);
+#[cfg(test)]
+const QUOTA_WAIT_TIMEOUT: Duration = Duration::from_millis(200);
+const QUOTA_WAIT_INTERVAL: Duration = Duration::from_millis(50);
+
 #[derive(Clone)]
 pub struct MemoryQuota {
     capacity: Arc<AtomicUsize>,
@@ -170,8 +179,13 @@ impl Sink {
         for event in &events {
             total_bytes += event.size();
         }
-        if !self.memory_quota.alloc(total_bytes as _) {
-            return Err(SendError::Congested);
+        let mut waited = Duration::default();
+        while !self.memory_quota.alloc(total_bytes as _) {
+            if waited >= QUOTA_WAIT_TIMEOUT {
+                return Err(SendError::Congested);
+            }
+            futures_timer::Delay::new(QUOTA_WAIT_INTERVAL).await;
+            waited += QUOTA_WAIT_INTERVAL;
         }
         for event in events {
             let bytes = event.size() as usize;
@@ -390,6 +404,28 @@ mod tests {
         assert_matches!(send(CdcEvent::Event(e)).unwrap_err(), SendError::Congested);
     }
 
+    #[test]
+    fn test_send_all_waits_for_quota() {
+        let mut e = kvproto::cdcpb::Event::default();
+        e.region_id = 1;
+        let event = CdcEvent::Event(e.clone());
+        assert!(event.size() != 0);
+        // 1KB
+        let max_pending_bytes = 1024;
+        let buffer = max_pending_bytes / event.size();
+        let memory_quota = MemoryQuota::new(max_pending_bytes as _);
+        let (mut tx, rx) = channel(buffer as _, memory_quota);
+        while tx.unbounded_send(CdcEvent::Event(e.clone()), false).is_ok() {}
+        // Freeing the quota while send_all is waiting unblocks it.
+        let memory_quota = rx.memory_quota.clone();
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            memory_quota.free(max_pending_bytes as _);
+        });
+        block_on(tx.send_all(vec![CdcEvent::Event(e)])).unwrap();
+        handle.join().unwrap();
+    }
+
     #[test]
     fn test_set_capacity() {
         let mut e = kvproto::cdcpb::Event::default();
@@ -546,11 +546,11 @@ impl Delegate {
         is_one_pc: bool,
     ) -> Result<()> {
         let txn_extra_op = self.txn_extra_op;
-        let mut read_old_value = |row: &mut EventRow, read_old_ts| {
+        let mut read_old_value = |row: &mut EventRow, read_old_ts| -> Result<()> {
             if txn_extra_op == TxnExtraOp::ReadOldValue {
                 let key = Key::from_raw(&row.key).append_ts(row.start_ts.into());
                 let start = Instant::now();
-                let (old_value, statistics) = old_value_cb(key, read_old_ts, old_value_cache);
+                let (old_value, statistics) = old_value_cb(key, read_old_ts, old_value_cache)?;
                 row.old_value = old_value.unwrap_or_default();
                 CDC_OLD_VALUE_DURATION_HISTOGRAM
                     .with_label_values(&["all"])
@@ -565,15 +565,32 @@ impl Delegate {
                     }
                 }
             }
+            Ok(())
         };
 
         let mut rows: HashMap<Vec<u8>, EventRow> = HashMap::default();
         for mut req in requests {
             match req.get_cmd_type() {
                 CmdType::Put => {
-                    self.sink_put(req.take_put(), is_one_pc, &mut rows, &mut read_old_value)
+                    self.sink_put(req.take_put(), is_one_pc, &mut rows, &mut read_old_value)?
                 }
                 CmdType::Delete => self.sink_delete(req.take_delete()),
+                CmdType::IngestSst => {
+                    // Ingested files bypass the normal write path, so their
+                    // content can not be emitted as delta events. Fail the
+                    // subscription and let downstreams reconnect, the
+                    // incremental scan after reconnecting covers the ingested
+                    // data.
+                    info!(
+                        "stop capturing the region due to ingest sst";
+                        "region_id" => self.region_id,
+                        "sst" => ?req.get_ingest_sst().get_sst(),
+                    );
+                    self.mark_failed();
+                    let store_err =
+                        RaftStoreError::EpochNotMatch("ingest sst".to_owned(), vec![]);
+                    return Err(Error::request(store_err.into()));
+                }
                 _ => {
                     debug!(
                         "skip other command";
@@ -632,20 +649,20 @@ impl Delegate {
         mut put: PutRequest,
         is_one_pc: bool,
         rows: &mut HashMap<Vec<u8>, EventRow>,
-        mut read_old_value: impl FnMut(&mut EventRow, /* read_old_ts */ TimeStamp),
-    ) {
+        mut read_old_value: impl FnMut(&mut EventRow, /* read_old_ts */ TimeStamp) -> Result<()>,
+    ) -> Result<()> {
         match put.cf.as_str() {
             "write" => {
                 let mut row = EventRow::default();
                 let skip = decode_write(put.take_key(), put.get_value(), &mut row, true);
                 if skip {
-                    return;
+                    return Ok(());
                 }
 
                 let commit_ts = if is_one_pc {
                     set_event_row_type(&mut row, EventLogType::Committed);
                     let commit_ts = TimeStamp::from(row.commit_ts);
-                    read_old_value(&mut row, commit_ts.prev());
+                    read_old_value(&mut row, commit_ts.prev())?;
                     Some(commit_ts)
                 } else {
                     // 2PC
@@ -682,11 +699,11 @@ impl Delegate {
                 let for_update_ts = lock.for_update_ts;
                 let skip = decode_lock(put.take_key(), lock, &mut row);
                 if skip {
-                    return;
+                    return Ok(());
                 }
 
                 let read_old_ts = std::cmp::max(for_update_ts, row.start_ts.into());
-                read_old_value(&mut row, read_old_ts);
+                read_old_value(&mut row, read_old_ts)?;
                 let occupied = rows.entry(row.key.clone()).or_default();
                 if !occupied.value.is_empty() {
                     assert!(row.value.is_empty());
@@ -724,6 +741,7 @@ impl Delegate {
                 panic!("invalid cf {}", other);
             }
         }
+        Ok(())
     }
 
     fn sink_delete(&mut self, mut delete: DeleteRequest) {
@@ -1193,6 +1193,9 @@ impl Initializer {
         let mut scanner = ScannerBuilder::new(snap, current)
             .fill_cache(false)
             .range(None, None)
+            // SST files with no commit ts after the checkpoint carry no deltas
+            // and can be skipped entirely.
+            .hint_min_ts(Some(self.checkpoint_ts.next()))
             .build_delta_scanner(self.checkpoint_ts, self.txn_extra_op)
             .unwrap();
         let conn_id = self.conn_id;
@@ -1219,6 +1222,11 @@ impl Initializer {
         }
 
         let takes = start.saturating_elapsed();
+        info!("cdc async incremental scan finished";
+            "region_id" => region_id,
+            "conn_id" => ?conn_id,
+            "downstream_id" => ?downstream_id,
+            "takes" => ?takes);
         if let Some(resolver) = resolver {
             self.finish_building_resolver(resolver, region, takes);
         }
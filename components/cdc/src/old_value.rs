@@ -14,8 +14,10 @@ use txn_types::{Key, MutationType, OldValue, TimeStamp, Value, WriteRef, WriteTy
 use crate::metrics::*;
 use crate::Result;
 
-pub(crate) type OldValueCallback =
-    Box<dyn Fn(Key, TimeStamp, &mut OldValueCache) -> (Option<Vec<u8>>, Option<Statistics>) + Send>;
+pub(crate) type OldValueCallback = Box<
+    dyn Fn(Key, TimeStamp, &mut OldValueCache) -> Result<(Option<Vec<u8>>, Option<Statistics>)>
+        + Send,
+>;
 
 #[derive(Default)]
 pub struct OldValueCacheSizePolicy(usize);
@@ -157,21 +159,21 @@ pub fn get_old_value<S: EngineSnapshot>(
     key: Key,
     query_ts: TimeStamp,
     old_value_cache: &mut OldValueCache,
-) -> (Option<Vec<u8>>, Option<Statistics>) {
+) -> Result<(Option<Vec<u8>>, Option<Statistics>)> {
     old_value_cache.access_count += 1;
     if let Some((old_value, mutation_type)) = old_value_cache.cache.remove(&key) {
         return match mutation_type {
             // Old value of an Insert is guaranteed to be None.
             Some(MutationType::Insert) => {
                 assert_eq!(old_value, OldValue::None);
-                (None, None)
+                Ok((None, None))
             }
             // For Put, Delete or a mutation type we do not know,
             // we read old value from the cache.
             Some(MutationType::Put) | Some(MutationType::Delete) | None => {
                 match old_value {
-                    OldValue::None => (None, None),
-                    OldValue::Value { value } => (Some(value), None),
+                    OldValue::None => Ok((None, None)),
+                    OldValue::Value { value } => Ok((Some(value), None)),
                     OldValue::ValueTimeStamp { start_ts } => {
                         let mut statistics = Statistics::default();
                         let prev_key = key.truncate_ts().unwrap().append_ts(start_ts);
@@ -182,7 +184,7 @@ pub fn get_old_value<S: EngineSnapshot>(
                         CDC_OLD_VALUE_DURATION_HISTOGRAM
                             .with_label_values(&["get"])
                             .observe(start.saturating_elapsed().as_secs_f64());
-                        (value, Some(statistics))
+                        Ok((value, Some(statistics)))
                     }
                     // Unspecified should not be added into cache.
                     OldValue::Unspecified => unreachable!(),
@@ -196,16 +198,14 @@ pub fn get_old_value<S: EngineSnapshot>(
     let mut statistics = Statistics::default();
     let start = Instant::now();
     let key = key.truncate_ts().unwrap().append_ts(query_ts);
-    let value = reader
-        .near_seek_old_value(&key, &mut statistics)
-        .unwrap_or_default();
+    let value = reader.near_seek_old_value(&key, &mut statistics)?;
     CDC_OLD_VALUE_DURATION_HISTOGRAM
         .with_label_values(&["seek"])
         .observe(start.saturating_elapsed().as_secs_f64());
     if value.is_none() {
         old_value_cache.miss_none_count += 1;
     }
-    (value, Some(statistics))
+    Ok((value, Some(statistics)))
 }
 
 #[cfg(test)]
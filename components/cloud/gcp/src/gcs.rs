@@ -344,6 +344,123 @@ impl GCSStorage {
     {
         Box::new(error_stream(io::Error::new(kind, e)).into_async_read())
     }
+
+    /// Initiates a resumable upload session and returns the session URI that
+    /// following chunk uploads must be sent to.
+    async fn initiate_resumable_upload(
+        &self,
+        metadata: &Metadata,
+        content_length: u64,
+    ) -> Result<String, RequestError> {
+        let name_query = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("name", metadata.name.as_deref().unwrap_or_default())
+            .finish();
+        let uri = format!(
+            "{}/upload/storage/v1/b/{}/o?uploadType=resumable&{}",
+            GOOGLE_APIS, self.config.bucket.bucket, name_query
+        );
+        let body = serde_json::to_vec(metadata)
+            .map_err(|e| RequestError::Gcs(tame_gcs::Error::Json(e)))?;
+        let req = Request::builder()
+            .method("POST")
+            .uri(&uri)
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .header("X-Upload-Content-Length", content_length)
+            .body(Body::from(body))
+            .map_err(|e| RequestError::Gcs(tame_gcs::Error::Http(e)))?;
+        let res = self.make_request(req, tame_gcs::Scopes::ReadWrite).await?;
+        res.headers()
+            .get(http::header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_owned())
+            .ok_or_else(|| {
+                status_code_error(
+                    StatusCode::BAD_REQUEST,
+                    "resumable upload session URI missing".to_string(),
+                )
+            })
+    }
+
+    /// Uploads one chunk of a resumable upload. Chunks except the last one
+    /// must have a length that is a multiple of 256 KiB. Re-uploading a chunk
+    /// with the same range is accepted by GCS, so a failed chunk can simply be
+    /// retried without restarting the whole upload.
+    async fn upload_resumable_chunk(
+        &self,
+        session_uri: &str,
+        data: &[u8],
+        offset: u64,
+        total: u64,
+    ) -> Result<(), RequestError> {
+        let last_byte = offset + data.len() as u64 - 1;
+        let mut req = Request::builder()
+            .method("PUT")
+            .uri(session_uri)
+            .header(http::header::CONTENT_LENGTH, data.len())
+            .header(
+                http::header::CONTENT_RANGE,
+                format!("bytes {}-{}/{}", offset, last_byte, total),
+            )
+            .body(Body::from(data.to_vec()))
+            .map_err(|e| RequestError::Gcs(tame_gcs::Error::Http(e)))?;
+        if let Some(svc_access) = &self.svc_access {
+            self.set_auth(&mut req, tame_gcs::Scopes::ReadWrite, svc_access.clone())
+                .await?;
+        }
+        let res = self
+            .client
+            .request(req)
+            .await
+            .map_err(|e| RequestError::Hyper(e, "upload resumable chunk".to_owned()))?;
+        // 308 signals that the chunk is persisted and more data is expected.
+        if res.status().is_success() || res.status() == StatusCode::PERMANENT_REDIRECT {
+            Ok(())
+        } else {
+            Err(status_code_error(
+                res.status(),
+                "upload resumable chunk".to_string(),
+            ))
+        }
+    }
+
+    /// Uploads a large object with the resumable upload protocol, so only one
+    /// chunk needs to be buffered in memory at a time and a chunk is the unit
+    /// of retry.
+    async fn resumable_upload(
+        &self,
+        metadata: &Metadata,
+        reader: &mut (dyn AsyncRead + Send + Unpin),
+        content_length: u64,
+    ) -> io::Result<()> {
+        let session_uri = retry(|| self.initiate_resumable_upload(metadata, content_length))
+            .await
+            .map_err(io::Error::from)?;
+        let mut buf = vec![0; RESUMABLE_CHUNK_SIZE];
+        let mut offset = 0;
+        while offset < content_length {
+            // Fill the chunk as full as possible: non-final chunks must have
+            // a length that is a multiple of 256 KiB.
+            let mut filled = 0;
+            while filled < buf.len() {
+                let read = reader.read(&mut buf[filled..]).await?;
+                if read == 0 {
+                    break;
+                }
+                filled += read;
+            }
+            if filled == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "resumable upload ends prematurely",
+                ));
+            }
+            retry(|| self.upload_resumable_chunk(&session_uri, &buf[..filled], offset, content_length))
+                .await
+                .map_err(io::Error::from)?;
+            offset += filled as u64;
+        }
+        Ok(())
+    }
 }
 
 fn change_host(host: &StringNonEmpty, url: &str) -> Option<String> {
@@ -390,6 +507,11 @@ fn parse_predefined_acl(acl: &str) -> Result<Option<PredefinedAcl>, &str> {
 
 const STORAGE_NAME: &str = "gcs";
 
+/// The size of a chunk of a resumable upload, and the threshold above which
+/// uploads become resumable instead of single-shot. GCS requires the length
+/// of non-final chunks to be a multiple of 256 KiB.
+const RESUMABLE_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
 impl BlobStorage for GCSStorage {
     fn config(&self) -> Box<dyn BlobConfig> {
         Box::new(self.config.clone()) as Box<dyn BlobConfig>
@@ -422,9 +544,17 @@ impl BlobStorage for GCSStorage {
             ..Default::default()
         };
 
+        if content_length > RESUMABLE_CHUNK_SIZE as u64 {
+            // Large files are streamed chunk by chunk with the resumable
+            // upload protocol, so they do not need to fit in memory and a
+            // failed chunk can be retried without restarting the upload.
+            return block_on_external_io(
+                self.resumable_upload(&metadata, &mut reader, content_length),
+            );
+        }
+
         block_on_external_io(async move {
-            // FIXME: Switch to upload() API so we don't need to read the entire data into memory
-            // in order to retry.
+            // Read the entire short file into memory in order to retry.
             let mut data = Vec::with_capacity(content_length as usize);
             reader.read_to_end(&mut data).await?;
             retry(|| async {
@@ -0,0 +1,260 @@
+// Copyright 2021 TiKV Project Authors. Licensed under Apache-2.0.
+
+use std::convert::TryInto;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use hyper::{client::HttpConnector, Body, Client, Request, Response, StatusCode};
+use hyper_tls::HttpsConnector;
+use rand::{rngs::OsRng, RngCore};
+use tame_oauth::gcp::{ServiceAccountAccess, ServiceAccountInfo, TokenOrRequest};
+
+use cloud::error::{Error, KmsError, Result};
+use cloud::kms::{Config, DataKeyPair, EncryptedKey, KeyId, KmsProvider, PlainKey};
+
+const CLOUD_KMS_ENDPOINT: &str = "https://cloudkms.googleapis.com";
+const CLOUD_KMS_SCOPE: &str = "https://www.googleapis.com/auth/cloudkms";
+// Cloud KMS does not have a generate-data-key API. The plaintext data key is
+// generated locally and wrapped by the KMS crypto key (envelope encryption),
+// so only a 256-bit key needs to be produced here.
+const GCP_KMS_DATA_KEY_SIZE: usize = 32;
+pub const ENCRYPTION_VENDOR_NAME_GCP_KMS: &str = "gcp";
+
+/// GcpKms is an implementation of the KmsProvider backed by Google Cloud KMS.
+///
+/// The configured key id must be the full resource name of a symmetric
+/// crypto key, i.e.
+/// `projects/{project}/locations/{location}/keyRings/{ring}/cryptoKeys/{key}`.
+pub struct GcpKms {
+    config: Config,
+    endpoint: String,
+    svc_access: Option<Arc<ServiceAccountAccess>>,
+    client: Client<HttpsConnector<HttpConnector>, Body>,
+}
+
+impl GcpKms {
+    pub fn new(config: Config) -> Result<GcpKms> {
+        validate_key_id(&config.key_id)?;
+        let endpoint = if config.location.endpoint.is_empty() {
+            CLOUD_KMS_ENDPOINT.to_owned()
+        } else {
+            config.location.endpoint.clone()
+        };
+        let svc_access = load_service_account_access()?;
+        let client = Client::builder().build(HttpsConnector::new());
+        Ok(GcpKms {
+            config,
+            endpoint,
+            svc_access: svc_access.map(Arc::new),
+            client,
+        })
+    }
+
+    async fn set_auth(&self, req: &mut Request<Body>) -> Result<()> {
+        let svc_access = match &self.svc_access {
+            Some(svc_access) => svc_access.clone(),
+            None => return Ok(()),
+        };
+        let token_or_request = svc_access
+            .get_token(&[CLOUD_KMS_SCOPE])
+            .map_err(|e| Error::ApiAuthentication(box_display_err(e)))?;
+        let token = match token_or_request {
+            TokenOrRequest::Token(token) => token,
+            TokenOrRequest::Request {
+                request,
+                scope_hash,
+                ..
+            } => {
+                let res = self
+                    .client
+                    .request(request.map(From::from))
+                    .await
+                    .map_err(|e| Error::ApiInternal(e.into()))?;
+                if !res.status().is_success() {
+                    return Err(status_code_error(res.status(), "get oauth token"));
+                }
+                let (parts, body) = res.into_parts();
+                let body = hyper::body::to_bytes(body)
+                    .await
+                    .map_err(|e| Error::ApiInternal(e.into()))?;
+                svc_access
+                    .parse_token_response(scope_hash, Response::from_parts(parts, body))
+                    .map_err(|e| Error::ApiAuthentication(box_display_err(e)))?
+            }
+        };
+        req.headers_mut().insert(
+            http::header::AUTHORIZATION,
+            token
+                .try_into()
+                .map_err(|e| Error::ApiAuthentication(box_display_err(e)))?,
+        );
+        Ok(())
+    }
+
+    // Issues a `:encrypt` or `:decrypt` request against the crypto key and
+    // returns the base64 payload of the named field in the response.
+    async fn do_key_request(&self, action: &str, body: String, field: &str) -> Result<Vec<u8>> {
+        let uri = format!(
+            "{}/v1/{}:{}",
+            self.endpoint,
+            self.config.key_id.deref(),
+            action
+        );
+        let mut req = Request::builder()
+            .method("POST")
+            .uri(&uri)
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .map_err(|e| Error::Other(e.into()))?;
+        self.set_auth(&mut req).await?;
+        let res = self
+            .client
+            .request(req)
+            .await
+            .map_err(|e| Error::ApiInternal(e.into()))?;
+        if !res.status().is_success() {
+            return Err(status_code_error(res.status(), action));
+        }
+        let body = hyper::body::to_bytes(res.into_body())
+            .await
+            .map_err(|e| Error::ApiInternal(e.into()))?;
+        let value: serde_json::Value =
+            serde_json::from_slice(&body).map_err(|e| Error::Other(e.into()))?;
+        let b64 = value
+            .get(field)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| missing_field_error(action, field))?;
+        base64::decode(b64).map_err(|e| Error::KmsError(KmsError::Other(e.into())))
+    }
+}
+
+impl std::fmt::Debug for GcpKms {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GcpKms")
+            .field("key_id", &self.config.key_id)
+            .field("endpoint", &self.endpoint)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl KmsProvider for GcpKms {
+    fn name(&self) -> &str {
+        ENCRYPTION_VENDOR_NAME_GCP_KMS
+    }
+
+    async fn generate_data_key(&self) -> Result<DataKeyPair> {
+        let mut plaintext_key = vec![0u8; GCP_KMS_DATA_KEY_SIZE];
+        OsRng.fill_bytes(&mut plaintext_key);
+        let body = serde_json::json!({ "plaintext": base64::encode(&plaintext_key) }).to_string();
+        let ciphertext_key = self.do_key_request("encrypt", body, "ciphertext").await?;
+        Ok(DataKeyPair {
+            encrypted: EncryptedKey::new(ciphertext_key)?,
+            plaintext: PlainKey::new(plaintext_key)?,
+        })
+    }
+
+    // On decrypt failure, the rule is to return WrongMasterKey error in case it is possible that
+    // a wrong master key has been used, or other error otherwise.
+    async fn decrypt_data_key(&self, data_key: &EncryptedKey) -> Result<Vec<u8>> {
+        let body = serde_json::json!({ "ciphertext": base64::encode(&**data_key) }).to_string();
+        self.do_key_request("decrypt", body, "plaintext")
+            .await
+            .map_err(|err| match err {
+                // Cloud KMS rejects ciphertext wrapped by a different crypto
+                // key with INVALID_ARGUMENT.
+                Error::KmsError(KmsError::Other(e)) => {
+                    Error::KmsError(KmsError::WrongMasterKey(e))
+                }
+                e => e,
+            })
+    }
+}
+
+fn validate_key_id(key_id: &KeyId) -> Result<()> {
+    // projects/{project}/locations/{location}/keyRings/{ring}/cryptoKeys/{key}
+    let parts: Vec<&str> = key_id.split('/').collect();
+    if parts.len() != 8
+        || parts[0] != "projects"
+        || parts[2] != "locations"
+        || parts[4] != "keyRings"
+        || parts[6] != "cryptoKeys"
+    {
+        return Err(Error::KmsError(KmsError::EmptyKey(format!(
+            "invalid GCP KMS key id, expect the full crypto key resource name: {}",
+            **key_id
+        ))));
+    }
+    Ok(())
+}
+
+// Credentials are loaded from the file referenced by the standard
+// GOOGLE_APPLICATION_CREDENTIALS environment variable. When it is unset,
+// requests are sent unauthenticated, which is only useful against emulators.
+fn load_service_account_access() -> Result<Option<ServiceAccountAccess>> {
+    let path = match std::env::var("GOOGLE_APPLICATION_CREDENTIALS") {
+        Ok(path) => path,
+        Err(_) => return Ok(None),
+    };
+    let cred = std::fs::read_to_string(path).map_err(Error::Io)?;
+    let svc_info = ServiceAccountInfo::deserialize(cred)
+        .map_err(|e| Error::ApiAuthentication(box_display_err(e)))?;
+    let svc_access = ServiceAccountAccess::new(svc_info)
+        .map_err(|e| Error::ApiAuthentication(box_display_err(e)))?;
+    Ok(Some(svc_access))
+}
+
+fn box_display_err<E: std::fmt::Display>(err: E) -> Box<dyn std::error::Error + Sync + Send> {
+    format!("{}", err).into()
+}
+
+fn status_code_error(code: StatusCode, msg: &str) -> Error {
+    let err = box_display_err(format!("GCP KMS {}: {}", msg, code));
+    match code.as_u16() {
+        401 | 403 => Error::ApiAuthentication(err),
+        404 => Error::ApiNotFound(err),
+        408 => Error::ApiTimeout(err),
+        _ if code.is_server_error() => Error::ApiInternal(err),
+        _ => Error::KmsError(KmsError::Other(err)),
+    }
+}
+
+fn missing_field_error(action: &str, field: &str) -> Error {
+    Error::KmsError(KmsError::Other(box_display_err(format!(
+        "GCP KMS {} response misses field {}",
+        action, field
+    ))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cloud::kms::Location;
+
+    #[test]
+    fn test_validate_key_id() {
+        let valid = KeyId::new(
+            "projects/p/locations/global/keyRings/r/cryptoKeys/k".to_owned(),
+        )
+        .unwrap();
+        validate_key_id(&valid).unwrap();
+        let invalid = KeyId::new("alias/test-key".to_owned()).unwrap();
+        validate_key_id(&invalid).unwrap_err();
+    }
+
+    #[test]
+    fn test_endpoint_override() {
+        let config = Config {
+            key_id: KeyId::new("projects/p/locations/global/keyRings/r/cryptoKeys/k".to_owned())
+                .unwrap(),
+            location: Location {
+                region: "global".to_owned(),
+                endpoint: "http://localhost:9090".to_owned(),
+            },
+            vendor: ENCRYPTION_VENDOR_NAME_GCP_KMS.to_owned(),
+        };
+        let kms = GcpKms::new(config).unwrap();
+        assert_eq!(kms.endpoint, "http://localhost:9090");
+    }
+}
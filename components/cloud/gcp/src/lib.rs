@@ -5,3 +5,6 @@ extern crate slog_global;
 
 mod gcs;
 pub use gcs::{Config, GCSStorage};
+
+mod kms;
+pub use kms::{GcpKms, ENCRYPTION_VENDOR_NAME_GCP_KMS};
@@ -9,8 +9,10 @@ use tikv_util::{error, info};
 
 #[cfg(feature = "cloud-aws")]
 use aws::{AwsKms, STORAGE_VENDOR_NAME_AWS};
-#[cfg(feature = "cloud-aws")]
+#[cfg(any(feature = "cloud-aws", feature = "cloud-gcp"))]
 use cloud::kms::Config as CloudConfig;
+#[cfg(feature = "cloud-gcp")]
+use gcp::{GcpKms, ENCRYPTION_VENDOR_NAME_GCP_KMS};
 use cloud::kms::{EncryptedKey as CloudEncryptedKey, KmsProvider as CloudKmsProvider};
 use cloud::Error as CloudError;
 #[cfg(feature = "cloud-aws")]
@@ -69,6 +71,15 @@ pub fn create_cloud_backend(config: &KmsConfig) -> Result<Box<dyn Backend>> {
             ));
             Ok(Box::new(KmsBackend::new(Box::new(kms_provider))?) as Box<dyn Backend>)
         }
+        #[cfg(feature = "cloud-gcp")]
+        ENCRYPTION_VENDOR_NAME_GCP_KMS => {
+            let conf = CloudConfig::from_proto(config.clone().into_proto())
+                .map_err(cloud_convert_error("gcp from proto".to_owned()))?;
+            let kms_provider = CloudKms(Box::new(
+                GcpKms::new(conf).map_err(cloud_convert_error("new GCP KMS".to_owned()))?,
+            ));
+            Ok(Box::new(KmsBackend::new(Box::new(kms_provider))?) as Box<dyn Backend>)
+        }
         provider => Err(Error::Other(box_err!("provider not found {}", provider))),
     }
 }
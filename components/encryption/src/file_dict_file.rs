@@ -45,6 +45,9 @@ pub struct FileDictionaryFile {
     file_rewrite_threshold: u64,
     // Record the number of `REMOVE` to determine whether compact the log.
     removed: u64,
+    // Record the number of records appended since the last rewrite, to determine
+    // whether the background compaction should rewrite the log.
+    appended: u64,
     // Record size of the file.
     file_size: usize,
 }
@@ -80,6 +83,7 @@ impl FileDictionaryFile {
             enable_log,
             file_rewrite_threshold,
             removed: 0,
+            appended: 0,
             file_size: 0,
         };
         file_dict_file.rewrite()?;
@@ -102,6 +106,7 @@ impl FileDictionaryFile {
             enable_log,
             file_rewrite_threshold,
             removed: 0,
+            appended: 0,
             file_size: 0,
         };
 
@@ -148,11 +153,44 @@ impl FileDictionaryFile {
         }
         // rough size, excluding EncryptedFile meta.
         self.file_size = file_dict_bytes.len();
+        self.removed = 0;
+        self.appended = 0;
         Ok(())
     }
 
+    /// Rewrite the log file if it has accumulated enough garbage records. It is
+    /// called periodically from the background key rotation worker, so that a
+    /// dictionary churned by file creates and deletes does not grow without
+    /// bound between foreground compactions.
+    pub fn maybe_compact(&mut self) -> Result<bool> {
+        if self.enable_log && self.appended > self.file_rewrite_threshold {
+            self.rewrite()?;
+            self.update_metrics();
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
     /// Recovery from the log file and return `FileDictionary`.
     pub fn recovery(&mut self) -> Result<FileDictionary> {
+        self.recovery_inner(false)
+    }
+
+    /// Repair mode of recovery. Corrupted records in the middle of the log are
+    /// discarded, together with every record after them, instead of marking the
+    /// store for panic. The salvaged dictionary is rewritten so the next
+    /// recovery starts from a clean snapshot. Since data files carry no
+    /// encryption header themselves, dropped entries can not be reconstructed
+    /// and the corresponding files will be treated as plaintext, so this must
+    /// only be used by offline tools under manual intervention.
+    pub fn repair(&mut self) -> Result<FileDictionary> {
+        let file_dict = self.recovery_inner(true)?;
+        self.rewrite()?;
+        self.update_metrics();
+        Ok(file_dict)
+    }
+
+    fn recovery_inner(&mut self, tolerant: bool) -> Result<FileDictionary> {
         let mut f = OpenOptions::new().read(true).open(self.file_path())?;
         let mut buf = Vec::new();
         f.read_to_end(&mut buf)?;
@@ -185,6 +223,13 @@ impl FileDictionaryFile {
                                 LogRecord::REMOVE => {
                                     let original = file_dict.files.remove(&file_name);
                                     if original.is_none() {
+                                        if tolerant {
+                                            warn!(
+                                                "repair: remove a null entry, ignored";
+                                                "file_name" => &file_name,
+                                            );
+                                            continue;
+                                        }
                                         return Err(box_err!(
                                             "Try to recovery from log file but remove a null entry, file name: {}",
                                             file_name
@@ -201,6 +246,15 @@ impl FileDictionaryFile {
                             break;
                         }
                         Err(e) => {
+                            if tolerant {
+                                warn!(
+                                    "repair: corrupted record in the middle of the log, \
+                                     discarded it and all records after it";
+                                    "err" => ?e,
+                                    "last_record_name" => &last_record_name,
+                                );
+                                break;
+                            }
                             // This error is unrecoverable and manual intervention is required.
                             set_panic_mark();
                             return Err(e);
@@ -235,6 +289,7 @@ impl FileDictionaryFile {
             file.write_all(&bytes)?;
             file.sync_all()?;
 
+            self.appended += 1;
             self.file_size += bytes.len();
             self.check_compact()?;
         } else {
@@ -256,6 +311,7 @@ impl FileDictionaryFile {
             file.sync_all()?;
 
             self.removed += 1;
+            self.appended += 1;
             self.file_size += bytes.len();
             self.check_compact()?;
         } else {
@@ -269,7 +325,6 @@ impl FileDictionaryFile {
     /// if compact is needed.
     fn check_compact(&mut self) -> Result<()> {
         if self.removed > self.file_rewrite_threshold {
-            self.removed = 0;
             self.rewrite()?;
         }
         Ok(())
@@ -609,6 +664,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_file_dict_file_maybe_compact() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let mut file_dict_file = FileDictionaryFile::new(
+            tempdir.path(),
+            "test_file_dict_file",
+            true, /*enable_log*/
+            2,    /*file_rewrite_threshold*/
+        )
+        .unwrap();
+
+        let info1 = create_file_info(1, EncryptionMethod::Aes256Ctr);
+        let info2 = create_file_info(2, EncryptionMethod::Aes256Ctr);
+        let info3 = create_file_info(3, EncryptionMethod::Aes256Ctr);
+        file_dict_file.insert("f1", &info1).unwrap();
+        file_dict_file.insert("f2", &info2).unwrap();
+        file_dict_file.insert("f3", &info3).unwrap();
+
+        // Three records have been appended, exceeding the threshold.
+        assert!(file_dict_file.maybe_compact().unwrap());
+        // The log is a fresh snapshot now, nothing to compact.
+        assert!(!file_dict_file.maybe_compact().unwrap());
+
+        let file_dict = file_dict_file.recovery().unwrap();
+        assert_eq!(*file_dict.files.get("f1").unwrap(), info1);
+        assert_eq!(*file_dict.files.get("f2").unwrap(), info2);
+        assert_eq!(*file_dict.files.get("f3").unwrap(), info3);
+        assert_eq!(file_dict.files.len(), 3);
+    }
+
+    #[test]
+    fn test_file_dict_file_repair_corrupted_record() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let mut file_dict_file = FileDictionaryFile::new(
+            tempdir.path(),
+            "test_file_dict_file",
+            true, /*enable_log*/
+            1000, /*file_rewrite_threshold*/
+        )
+        .unwrap();
+
+        let info1 = create_file_info(1, EncryptionMethod::Aes256Ctr);
+        let info2 = create_file_info(2, EncryptionMethod::Aes256Ctr);
+        let info3 = create_file_info(3, EncryptionMethod::Aes256Ctr);
+        file_dict_file.insert("f1", &info1).unwrap();
+        file_dict_file.insert("f2", &info2).unwrap();
+        file_dict_file.insert("f3", &info3).unwrap();
+
+        // Corrupt the last byte of the second record, in the middle of the log.
+        let record3_len =
+            FileDictionaryFile::convert_record_to_bytes("f3", LogRecord::INSERT(info3))
+                .unwrap()
+                .len();
+        let path = file_dict_file.file_path();
+        let mut buf = std::fs::read(&path).unwrap();
+        let corrupt_at = buf.len() - record3_len - 1;
+        buf[corrupt_at] ^= 0xff;
+        std::fs::write(&path, &buf).unwrap();
+
+        // Repair salvages the records before the corrupted one and rewrites
+        // the log, so a following recovery succeeds.
+        let file_dict = file_dict_file.repair().unwrap();
+        assert_eq!(*file_dict.files.get("f1").unwrap(), info1);
+        assert_eq!(file_dict.files.get("f2"), None);
+        assert_eq!(file_dict.files.len(), 1);
+
+        let file_dict = file_dict_file.recovery().unwrap();
+        assert_eq!(*file_dict.files.get("f1").unwrap(), info1);
+        assert_eq!(file_dict.files.len(), 1);
+    }
+
     fn create_file_info(id: u64, method: EncryptionMethod) -> FileInfo {
         FileInfo {
             key_id: id,
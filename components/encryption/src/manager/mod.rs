@@ -335,6 +335,14 @@ impl Dicts {
         };
         self.rotate_key(key_id, data_key, master_key)
     }
+
+    fn maybe_compact_file_dict(&self) -> Result<()> {
+        let mut file_dict_file = self.file_dict_file.lock().unwrap();
+        if file_dict_file.maybe_compact()? {
+            info!("compacted file dictionary log in the background");
+        }
+        Ok(())
+    }
 }
 
 fn check_stale_file_exist(
@@ -376,6 +384,8 @@ fn run_background_rotate_work(
                 info!("Try to rotate data key, current method:{:?}", method);
                 dict.maybe_rotate_data_key(method, master_key)
                     .expect("Rotating key operation encountered error in the background worker");
+                dict.maybe_compact_file_dict()
+                    .expect("Compacting file dictionary encountered error in the background worker");
             },
             recv(terminal_recv) -> _ => {
                 info!("Key rotate worker has been cancelled.");
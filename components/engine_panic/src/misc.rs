@@ -32,6 +32,10 @@ impl MiscExt for PanicEngine {
         panic!()
     }
 
+    fn prefetch_range(&self, cf: &str, range: &Range) -> Result<()> {
+        panic!()
+    }
+
     fn path(&self) -> &str {
         panic!()
     }
@@ -69,6 +73,15 @@ impl MiscExt for PanicEngine {
         panic!()
     }
 
+    fn get_range_num_range_deletions(
+        &self,
+        cf: &str,
+        start: &[u8],
+        end: &[u8],
+    ) -> Result<Option<u64>> {
+        panic!()
+    }
+
     fn get_cf_num_files_at_level(&self, cf: &str, level: usize) -> Result<Option<u64>> {
         panic!()
     }
@@ -2,13 +2,77 @@
 
 use crate::rocks_metrics::*;
 
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use engine_traits::EngineEventListener;
 use file_system::{get_io_type, set_io_type, IOType};
+use lazy_static::lazy_static;
 use rocksdb::{
     CompactionJobInfo, DBBackgroundErrorReason, FlushJobInfo, IngestionInfo, SubcompactionJobInfo,
     WriteStallInfo,
 };
+use serde::Serialize;
 use tikv_util::set_panic_mark;
 
+/// How many recent stall condition changes are kept for inspection.
+const STALL_EVENT_CAPACITY: usize = 128;
+
+/// One stall condition change of a column family, kept so operators can see
+/// when and where writes slowed down without grepping the RocksDB LOG.
+#[derive(Clone, Serialize)]
+pub struct StallEvent {
+    pub db_name: String,
+    pub cf_name: String,
+    /// Unix timestamp in seconds at which the condition changed.
+    pub time: u64,
+}
+
+lazy_static! {
+    static ref STALL_EVENTS: Mutex<VecDeque<StallEvent>> =
+        Mutex::new(VecDeque::with_capacity(STALL_EVENT_CAPACITY));
+}
+
+fn record_stall_event(db_name: &str, cf_name: &str) {
+    let event = StallEvent {
+        db_name: db_name.to_owned(),
+        cf_name: cf_name.to_owned(),
+        time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+    };
+    let mut events = STALL_EVENTS.lock().unwrap();
+    if events.len() >= STALL_EVENT_CAPACITY {
+        events.pop_front();
+    }
+    events.push_back(event);
+}
+
+/// Returns the recent stall condition changes, oldest first.
+pub fn dump_stall_events() -> Vec<StallEvent> {
+    STALL_EVENTS.lock().unwrap().iter().cloned().collect()
+}
+
+lazy_static! {
+    static ref ENGINE_EVENT_LISTENERS: RwLock<Vec<Arc<dyn EngineEventListener>>> =
+        RwLock::new(Vec::new());
+}
+
+/// Registers a listener for the engine-neutral `engine_traits` events that
+/// `RocksEventListener` translates its RocksDB callbacks into. Listeners are
+/// process-wide and live for the rest of the process, like the RocksDB
+/// listener itself, which is installed when the DB is opened.
+pub fn register_engine_event_listener(listener: Arc<dyn EngineEventListener>) {
+    ENGINE_EVENT_LISTENERS.write().unwrap().push(listener);
+}
+
+fn for_each_listener(f: impl Fn(&dyn EngineEventListener)) {
+    for l in ENGINE_EVENT_LISTENERS.read().unwrap().iter() {
+        f(l.as_ref());
+    }
+}
+
 pub struct RocksEventListener {
     db_name: String,
 }
@@ -33,6 +97,7 @@ impl rocksdb::EventListener for RocksEventListener {
         if get_io_type() == IOType::Flush {
             set_io_type(IOType::Other);
         }
+        for_each_listener(|l| l.on_flush_completed(info.cf_name()));
     }
 
     fn on_compaction_begin(&self, info: &CompactionJobInfo) {
@@ -65,6 +130,13 @@ impl rocksdb::EventListener for RocksEventListener {
         {
             set_io_type(IOType::Other);
         }
+        for_each_listener(|l| {
+            l.on_compaction_completed(
+                info.cf_name(),
+                info.total_input_bytes(),
+                info.total_output_bytes(),
+            )
+        });
     }
 
     fn on_subcompaction_begin(&self, info: &SubcompactionJobInfo) {
@@ -90,6 +162,7 @@ impl rocksdb::EventListener for RocksEventListener {
         STORE_ENGINE_INGESTION_PICKED_LEVEL_VEC
             .with_label_values(&[&self.db_name, info.cf_name()])
             .observe(info.picked_level() as f64);
+        for_each_listener(|l| l.on_external_file_ingested(info.cf_name(), info.picked_level()));
     }
 
     fn on_background_error(&self, reason: DBBackgroundErrorReason, result: Result<(), String>) {
@@ -116,5 +189,7 @@ impl rocksdb::EventListener for RocksEventListener {
         STORE_ENGINE_EVENT_COUNTER_VEC
             .with_label_values(&[&self.db_name, info.cf_name(), "stall_conditions_changed"])
             .inc();
+        record_stall_event(&self.db_name, info.cf_name());
+        for_each_listener(|l| l.on_stall_conditions_changed(info.cf_name()));
     }
 }
@@ -243,6 +243,25 @@ impl MiscExt for RocksEngine {
         Ok(())
     }
 
+    fn prefetch_range(&self, cf: &str, range: &Range) -> Result<()> {
+        let start = KeyBuilder::from_slice(range.start_key, 0, 0);
+        let end = KeyBuilder::from_slice(range.end_key, 0, 0);
+        // Iterating the range with `fill_cache` loads its blocks into the
+        // block cache as a side effect, which is all this method is for.
+        let mut opts = IterOptions::new(Some(start), Some(end), true);
+        if self.is_titan() {
+            // Blob files have their own cache, iterating them here would only
+            // read the whole value log through.
+            opts.set_key_only(true);
+        }
+        let mut it = self.iterator_cf_opt(cf, opts)?;
+        let mut it_valid = it.seek(range.start_key.into())?;
+        while it_valid {
+            it_valid = it.next()?;
+        }
+        Ok(())
+    }
+
     fn path(&self) -> &str {
         self.as_inner().path()
     }
@@ -316,6 +335,17 @@ impl MiscExt for RocksEngine {
         ))
     }
 
+    fn get_range_num_range_deletions(
+        &self,
+        cf: &str,
+        start: &[u8],
+        end: &[u8],
+    ) -> Result<Option<u64>> {
+        Ok(crate::properties::get_range_num_range_deletions(
+            self, cf, start, end,
+        ))
+    }
+
     fn get_cf_num_files_at_level(&self, cf: &str, level: usize) -> Result<Option<u64>> {
         let handle = util::get_cf_handle(self.as_inner(), cf)?;
         Ok(crate::util::get_cf_num_files_at_level(
@@ -22,6 +22,7 @@ use crate::mvcc_properties::*;
 const PROP_TOTAL_SIZE: &str = "tikv.total_size";
 const PROP_SIZE_INDEX: &str = "tikv.size_index";
 const PROP_RANGE_INDEX: &str = "tikv.range_index";
+const PROP_NUM_RANGE_DELETIONS: &str = "tikv.num_range_deletions";
 pub const DEFAULT_PROP_SIZE_INDEX_DISTANCE: u64 = 4 * 1024 * 1024;
 pub const DEFAULT_PROP_KEYS_INDEX_DISTANCE: u64 = 40 * 1024;
 
@@ -320,6 +321,7 @@ pub struct RangePropertiesCollector {
     cur_offsets: RangeOffsets,
     prop_size_index_distance: u64,
     prop_keys_index_distance: u64,
+    num_range_deletions: u64,
 }
 
 impl Default for RangePropertiesCollector {
@@ -331,6 +333,7 @@ impl Default for RangePropertiesCollector {
             cur_offsets: RangeOffsets::default(),
             prop_size_index_distance: DEFAULT_PROP_SIZE_INDEX_DISTANCE,
             prop_keys_index_distance: DEFAULT_PROP_KEYS_INDEX_DISTANCE,
+            num_range_deletions: 0,
         }
     }
 }
@@ -360,6 +363,10 @@ impl RangePropertiesCollector {
 
 impl TablePropertiesCollector for RangePropertiesCollector {
     fn add(&mut self, key: &[u8], value: &[u8], entry_type: DBEntryType, _: u64, _: u64) {
+        if entry_type == DBEntryType::RangeDeletion {
+            self.num_range_deletions += 1;
+            return;
+        }
         // size
         let size = match get_entry_size(value, entry_type) {
             Ok(entry_size) => key.len() as u64 + entry_size,
@@ -384,7 +391,9 @@ impl TablePropertiesCollector for RangePropertiesCollector {
             let key = self.last_key.clone();
             self.insert_new_point(key);
         }
-        self.props.encode().0
+        let mut props = self.props.encode();
+        props.encode_u64(PROP_NUM_RANGE_DELETIONS, self.num_range_deletions);
+        props.0
     }
 }
 
@@ -559,6 +568,34 @@ pub fn get_range_entries_and_versions(
     Some((num_entries, props.num_versions))
 }
 
+pub fn get_range_num_range_deletions(
+    engine: &crate::RocksEngine,
+    cf: &str,
+    start: &[u8],
+    end: &[u8],
+) -> Option<u64> {
+    let range = Range::new(start, end);
+    let collection = match engine.get_properties_of_tables_in_range(cf, &[range]) {
+        Ok(v) => v,
+        Err(_) => return None,
+    };
+
+    if collection.is_empty() {
+        return None;
+    }
+
+    // SSTs written before the property was collected simply don't count.
+    let mut num_range_deletions = 0;
+    for (_, v) in collection.iter() {
+        num_range_deletions += v
+            .user_collected_properties()
+            .decode_u64(PROP_NUM_RANGE_DELETIONS)
+            .unwrap_or(0);
+    }
+
+    Some(num_range_deletions)
+}
+
 #[cfg(test)]
 mod tests {
     use rand::Rng;
@@ -0,0 +1,29 @@
+// Copyright 2021 TiKV Project Authors. Licensed under Apache-2.0.
+
+//! Subscription to engine events.
+
+/// A listener for events emitted by the engine's background work.
+///
+/// Components that react to flushes, compactions, ingestions or write stalls
+/// can implement this instead of polling engine properties. The payloads are
+/// deliberately engine-neutral: a column family name plus a few numbers.
+/// Listeners are registered with the concrete engine crate (registration is
+/// engine-specific, as the underlying engine may require it to be set up
+/// around engine construction), which translates its native callbacks into
+/// calls on this trait.
+///
+/// Callbacks are invoked from engine background threads and must not block.
+pub trait EngineEventListener: Send + Sync {
+    /// A memtable of `cf` was flushed into a level 0 file.
+    fn on_flush_completed(&self, _cf: &str) {}
+
+    /// A compaction in `cf` finished, having read `input_bytes` and written
+    /// `output_bytes`.
+    fn on_compaction_completed(&self, _cf: &str, _input_bytes: u64, _output_bytes: u64) {}
+
+    /// An external SST file was ingested into `cf` at level `picked_level`.
+    fn on_external_file_ingested(&self, _cf: &str, _picked_level: i32) {}
+
+    /// The write stall conditions of `cf` changed.
+    fn on_stall_conditions_changed(&self, _cf: &str) {}
+}
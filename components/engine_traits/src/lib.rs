@@ -114,6 +114,14 @@
 //! - All engines use the same error type, defined in this crate. Thus
 //!   engine-specific type information is boxed and hidden.
 //!
+//! - Engine event callbacks (compaction finished, flush finished, ingestion,
+//!   stall changes) are abstracted by `EngineEventListener`, which carries
+//!   engine-neutral payloads. Registration stays engine-specific because the
+//!   underlying engine may require its native listener to be set up before
+//!   the `KvEngine` value exists; consumers that need the full RocksDB-shaped
+//!   payloads — flow control, the compacted-event notifications to raftstore
+//!   — still register directly with `engine_rocks`.
+//!
 //! - `KvEngine` is a factory type for some of its associated types, but not
 //!   others. For now, use factory methods when RocksDB would require factory
 //!   method (that is, when the DB is required to create the associated type -
@@ -308,6 +316,8 @@ pub use crate::perf_context::*;
 // These modules contain more general traits, some of which may be implemented
 // by multiple types.
 
+mod event_listener;
+pub use crate::event_listener::*;
 mod iterable;
 pub use crate::iterable::*;
 mod mutable;
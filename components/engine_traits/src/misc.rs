@@ -55,6 +55,11 @@ pub trait MiscExt: CFNamesExt {
     /// Ref: <https://github.com/facebook/rocksdb/wiki/Delete-A-Range-Of-Keys>
     fn roughly_cleanup_ranges(&self, ranges: &[(Vec<u8>, Vec<u8>)]) -> Result<()>;
 
+    /// Reads the given range through once so that its blocks are loaded into
+    /// the block cache. It is used to warm up a cold range, e.g. right after
+    /// its peer becomes leader.
+    fn prefetch_range(&self, cf: &str, range: &Range) -> Result<()>;
+
     /// The path to the directory on the filesystem where the database is stored
     fn path(&self) -> &str;
 
@@ -81,6 +86,16 @@ pub trait MiscExt: CFNamesExt {
         end: &[u8],
     ) -> Result<Option<(u64, u64)>>;
 
+    /// Returns the number of range tombstones (left by `DeleteRange`) recorded
+    /// in the table properties of the SST files overlapping the range, or
+    /// `None` if no SST file overlaps it.
+    fn get_range_num_range_deletions(
+        &self,
+        cf: &str,
+        start: &[u8],
+        end: &[u8],
+    ) -> Result<Option<u64>>;
+
     fn get_cf_num_files_at_level(&self, cf: &str, level: usize) -> Result<Option<u64>>;
 
     fn get_cf_num_immutable_mem_table(&self, cf: &str) -> Result<Option<u64>>;
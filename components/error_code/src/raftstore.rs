@@ -30,7 +30,8 @@ define_error_codes!(
 
     SNAP_ABORT => ("SnapAbort", "", ""),
     SNAP_TOO_MANY => ("SnapTooMany", "", ""),
-    SNAP_UNKNOWN => ("SnapUnknown", "", "")
+    SNAP_UNKNOWN => ("SnapUnknown", "", ""),
+    SNAP_NO_SPACE => ("SnapNoSpace", "", "")
 );
 
 impl ErrorCodeExt for errorpb::Error {
@@ -10,6 +10,7 @@ define_error_codes!(
     SCHED_TOO_BUSY => ("SchedTooBusy", "", ""),
     GC_WORKER_TOO_BUSY => ("GcWorkerTooBusy", "", ""),
     KEY_TOO_LARGE => ("KeyTooLarge", "", ""),
+    VALUE_TOO_LARGE => ("ValueTooLarge", "", ""),
     INVALID_CF => ("InvalidCF", "", ""),
     TTL_NOT_ENABLED => ("TTLNotEnabled", "", ""),
     PROTOBUF => ("Protobuf", "", ""),
@@ -8,6 +8,7 @@ use std::io::{self, Read, Seek, Write};
 use std::path::Path;
 use std::sync::Arc;
 
+use fail::fail_point;
 use fs2::FileExt;
 
 /// A wrapper around `std::fs::File` with capability to track and regulate IO flow.
@@ -95,6 +96,12 @@ impl File {
 
 impl Read for File {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // `delay` and `pause` actions can be injected here to simulate slow
+        // disks in chaos tests.
+        fail_point!("file_read_io_error", |_| Err(io::Error::new(
+            io::ErrorKind::Other,
+            "injected io error",
+        )));
         if let Some(limiter) = &mut self.limiter {
             let mut remains = buf.len();
             let mut pos = 0;
@@ -122,6 +129,17 @@ impl Seek for File {
 
 impl Write for File {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // `delay` and `pause` actions can be injected here to simulate slow
+        // disks in chaos tests.
+        fail_point!("file_write_io_error", |_| Err(io::Error::new(
+            io::ErrorKind::Other,
+            "injected io error",
+        )));
+        // Simulates a short write where the kernel accepts only part of the
+        // buffer.
+        fail_point!("file_write_short", !buf.is_empty(), |_| self
+            .inner
+            .write(&buf[..1]));
         if let Some(limiter) = &mut self.limiter {
             let mut remains = buf.len();
             let mut pos = 0;
@@ -473,6 +473,10 @@ impl IORateLimiter {
         self.priority_map[io_type as usize].store(io_priority as u32, Ordering::Relaxed);
     }
 
+    pub fn io_priority(&self, io_type: IOType) -> IOPriority {
+        IOPriority::unsafe_from_u32(self.priority_map[io_type as usize].load(Ordering::Relaxed))
+    }
+
     pub fn set_low_priority_io_adjustor_if_needed(
         &self,
         adjustor: Option<Arc<dyn IOBudgetAdjustor>>,
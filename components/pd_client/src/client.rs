@@ -573,6 +573,9 @@ impl PdClient for RpcClient {
                             info!("cancel region heartbeat sender");
                         }
                         Err(e) => {
+                            PD_HEARTBEAT_COUNTER_VEC
+                                .with_label_values(&["stream_err"])
+                                .inc();
                             error!(?e; "failed to send heartbeat");
                         }
                     };
@@ -588,7 +591,12 @@ impl PdClient for RpcClient {
                 .expect("expect region heartbeat sender");
             let ret = sender
                 .unbounded_send(req)
-                .map_err(|e| Error::Other(Box::new(e)));
+                .map_err(|e| {
+                    PD_HEARTBEAT_COUNTER_VEC
+                        .with_label_values(&["schedule_err"])
+                        .inc();
+                    Error::Other(Box::new(e))
+                });
             Box::pin(future::ready(ret)) as PdFuture<_>
         };
 
@@ -19,6 +19,7 @@ pub use self::util::REQUEST_RECONNECT_INTERVAL;
 
 use std::ops::Deref;
 
+use futures::executor::block_on;
 use futures::future::BoxFuture;
 use kvproto::metapb;
 use kvproto::pdpb;
@@ -260,6 +261,14 @@ pub trait PdClient: Send + Sync {
         unimplemented!()
     }
 
+    /// Gets a timestamp from PD, blocking the current thread.
+    ///
+    /// Timestamps are allocated from locally cached batches when possible, so
+    /// this is cheap to call outside of an async context.
+    fn get_tso_sync(&self) -> Result<TimeStamp> {
+        block_on(self.get_tso())
+    }
+
     /// Gets the internal `FeatureGate`.
     fn feature_gate(&self) -> &FeatureGate {
         unimplemented!()
@@ -732,17 +732,20 @@ impl PdConnector {
                     let client_urls = leader.get_client_urls();
                     for leader_url in client_urls {
                         let target = TargetInfo::new(leader_url.clone(), &ep);
-                        let response = client
-                            .get_members_async_opt(
-                                &GetMembersRequest::default(),
-                                target
-                                    .call_option()
-                                    .timeout(Duration::from_secs(REQUEST_TIMEOUT)),
-                            )
-                            .unwrap_or_else(|e| {
-                                panic!("fail to request PD {} err {:?}", "get_members", e)
-                            })
-                            .await;
+                        // We are probing followers exactly because the network is
+                        // unhealthy, so a failure here must not bring the store down.
+                        let response = match client.get_members_async_opt(
+                            &GetMembersRequest::default(),
+                            target
+                                .call_option()
+                                .timeout(Duration::from_secs(REQUEST_TIMEOUT)),
+                        ) {
+                            Ok(resp) => resp.await,
+                            Err(e) => {
+                                error!("fail to request PD member"; "endpoints" => %ep, "err" => ?e);
+                                continue;
+                            }
+                        };
                         match response {
                             Ok(_) => return Ok(Some((client, target))),
                             Err(_) => continue,
@@ -35,6 +35,13 @@ pub struct Config {
     pub region_max_keys: u64,
     pub region_split_keys: u64,
 
+    /// Whether to collect region buckets during full split check scans.
+    /// Buckets split a region into sub-ranges of about region_bucket_size so
+    /// that big regions don't have to be treated as one opaque unit by
+    /// hot spot diagnostics.
+    pub enable_region_bucket: bool,
+    pub region_bucket_size: ReadableSize,
+
     /// ConsistencyCheckMethod can not be chanaged dynamically.
     #[online_config(skip)]
     pub consistency_check_method: ConsistencyCheckMethod,
@@ -61,6 +68,8 @@ pub const SPLIT_SIZE_MB: u64 = 96;
 pub const SPLIT_KEYS: u64 = 960000;
 /// Default batch split limit.
 pub const BATCH_SPLIT_LIMIT: u64 = 10;
+/// Default region bucket size.
+pub const BUCKET_SIZE_MB: u64 = 96;
 
 impl Default for Config {
     fn default() -> Config {
@@ -72,6 +81,8 @@ impl Default for Config {
             region_max_size: split_size / 2 * 3,
             region_split_keys: SPLIT_KEYS,
             region_max_keys: SPLIT_KEYS / 2 * 3,
+            enable_region_bucket: false,
+            region_bucket_size: ReadableSize::mb(BUCKET_SIZE_MB),
             consistency_check_method: ConsistencyCheckMethod::Mvcc,
             perf_level: PerfLevel::EnableCount,
         }
@@ -94,6 +105,9 @@ impl Config {
                 self.region_split_keys
             ));
         }
+        if self.enable_region_bucket && self.region_bucket_size.0 == 0 {
+            return Err(box_err!("region_bucket_size cannot be 0 when region buckets are enabled"));
+        }
         Ok(())
     }
 }
@@ -136,5 +150,10 @@ mod tests {
         cfg.region_max_keys = 10;
         cfg.region_split_keys = 20;
         assert!(cfg.validate().is_err());
+
+        cfg = Config::default();
+        cfg.enable_region_bucket = true;
+        cfg.region_bucket_size = ReadableSize(0);
+        assert!(cfg.validate().is_err());
     }
 }
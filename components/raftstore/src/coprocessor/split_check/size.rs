@@ -411,6 +411,69 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_region_buckets() {
+        let path = Builder::new().prefix("test-raftstore").tempdir().unwrap();
+        let path_str = path.path().to_str().unwrap();
+        let db_opts = DBOptions::new();
+        let cfs_opts = ALL_CFS
+            .iter()
+            .map(|cf| CFOptions::new(cf, ColumnFamilyOptions::new()))
+            .collect();
+        let engine = engine_test::kv::new_engine_opt(path_str, db_opts, cfs_opts).unwrap();
+
+        let mut region = Region::default();
+        region.set_id(1);
+        region.mut_peers().push(Peer::default());
+        region.mut_region_epoch().set_version(2);
+        region.mut_region_epoch().set_conf_ver(5);
+
+        let (tx, rx) = mpsc::sync_channel(100);
+        let cfg = Config {
+            region_max_size: ReadableSize(100),
+            region_split_size: ReadableSize(60),
+            batch_split_limit: 5,
+            enable_region_bucket: true,
+            region_bucket_size: ReadableSize(40),
+            ..Default::default()
+        };
+
+        let mut runnable =
+            SplitCheckRunner::new(engine.clone(), tx.clone(), CoprocessorHost::new(tx, cfg));
+
+        // Every entry takes 10 bytes, so the region is scanned into buckets of
+        // 4 entries each.
+        for i in 0..12 {
+            let s = keys::data_key(format!("{:04}", i).as_bytes());
+            engine.put_cf(CF_WRITE, &s, &s).unwrap();
+        }
+        engine.flush_cf(CF_WRITE, true).unwrap();
+
+        runnable.run(SplitCheckTask::split_check(
+            region.clone(),
+            true,
+            CheckPolicy::Scan,
+        ));
+        loop {
+            match rx.try_recv() {
+                Ok((region_id, CasualMessage::RefreshRegionBuckets { buckets, .. })) => {
+                    assert_eq!(region_id, region.get_id());
+                    assert_eq!(buckets.len(), 3);
+                    assert_eq!(buckets[0].start_key, region.get_start_key().to_vec());
+                    assert_eq!(buckets[1].start_key, b"0004".to_vec());
+                    assert_eq!(buckets[2].start_key, b"0008".to_vec());
+                    for bucket in buckets {
+                        assert_eq!(bucket.size, 40);
+                        assert_eq!(bucket.keys, 4);
+                    }
+                    break;
+                }
+                Ok(_) => (),
+                others => panic!("expect region buckets, but got {:?}", others),
+            }
+        }
+    }
+
     #[test]
     fn test_cf_lock_without_range_prop() {
         let path = Builder::new().prefix("test-raftstore").tempdir().unwrap();
@@ -95,12 +95,15 @@ pub struct Config {
     /// Minimum percentage of tombstones to trigger manual compaction.
     /// Should between 1 and 100.
     pub region_compact_tombstones_percent: u64,
+    /// Minimum number of DeleteRange tombstones to trigger manual compaction.
+    pub region_compact_min_range_tombstones: u64,
     pub pd_heartbeat_tick_interval: ReadableDuration,
     pub pd_store_heartbeat_tick_interval: ReadableDuration,
     pub snap_mgr_gc_tick_interval: ReadableDuration,
     pub snap_gc_timeout: ReadableDuration,
     pub lock_cf_compact_interval: ReadableDuration,
     pub lock_cf_compact_bytes_threshold: ReadableSize,
+    pub lock_cf_compact_tombstones_threshold: u64,
 
     #[online_config(skip)]
     pub notify_capacity: usize,
@@ -125,6 +128,11 @@ pub struct Config {
     #[online_config(skip)]
     pub snap_apply_batch_size: ReadableSize,
 
+    /// Whether to load the data of a region into the block cache when its
+    /// peer becomes leader, so the first reads after a leader transfer don't
+    /// hit cold disk.
+    pub prefetch_region_on_become_leader: bool,
+
     // Interval (ms) to check region whether the data is consistent.
     pub consistency_check_interval: ReadableDuration,
 
@@ -177,6 +185,11 @@ pub struct Config {
     pub dev_assert: bool,
     #[online_config(hidden)]
     pub apply_yield_duration: ReadableDuration,
+    /// Maximum bytes of writes from different regions merged into one RocksDB
+    /// write batch on the apply path. A larger value amortizes more WAL sync
+    /// cost at the price of more write latency jitter.
+    #[online_config(hidden)]
+    pub apply_max_wb_bytes: ReadableSize,
 
     #[serde(with = "engine_config::perf_level_serde")]
     #[online_config(skip)]
@@ -255,6 +268,7 @@ impl Default for Config {
             region_compact_check_step: 100,
             region_compact_min_tombstones: 10000,
             region_compact_tombstones_percent: 30,
+            region_compact_min_range_tombstones: 100,
             pd_heartbeat_tick_interval: ReadableDuration::minutes(1),
             pd_store_heartbeat_tick_interval: ReadableDuration::secs(10),
             notify_capacity: 40960,
@@ -267,8 +281,10 @@ impl Default for Config {
             peer_stale_state_check_interval: ReadableDuration::minutes(5),
             leader_transfer_max_log_lag: 128,
             snap_apply_batch_size: ReadableSize::mb(10),
+            prefetch_region_on_become_leader: false,
             lock_cf_compact_interval: ReadableDuration::minutes(10),
             lock_cf_compact_bytes_threshold: ReadableSize::mb(256),
+            lock_cf_compact_tombstones_threshold: 100000,
             // Disable consistency check by default as it will hurt performance.
             // We should turn on this only in our tests.
             consistency_check_interval: ReadableDuration::secs(0),
@@ -289,6 +305,7 @@ impl Default for Config {
             hibernate_regions: true,
             dev_assert: false,
             apply_yield_duration: ReadableDuration::millis(500),
+            apply_max_wb_bytes: ReadableSize::mb(32),
             perf_level: PerfLevel::EnableTime,
             evict_cache_on_memory_ratio: 0.2,
             cmd_batch: true,
@@ -456,6 +473,9 @@ impl Config {
         } else {
             self.apply_batch_system.max_batch_size = Some(256);
         }
+        if self.apply_max_wb_bytes.0 == 0 {
+            return Err(box_err!("apply-max-wb-bytes should be greater than 0"));
+        }
         if self.store_batch_system.pool_size == 0 {
             return Err(box_err!("store-pool-size should be greater than 0"));
         }
@@ -569,6 +589,9 @@ impl Config {
         CONFIG_RAFTSTORE_GAUGE
             .with_label_values(&["region_compact_tombstones_percent"])
             .set(self.region_compact_tombstones_percent as f64);
+        CONFIG_RAFTSTORE_GAUGE
+            .with_label_values(&["region_compact_min_range_tombstones"])
+            .set(self.region_compact_min_range_tombstones as f64);
         CONFIG_RAFTSTORE_GAUGE
             .with_label_values(&["pd_heartbeat_tick_interval"])
             .set(self.pd_heartbeat_tick_interval.as_secs() as f64);
@@ -587,6 +610,9 @@ impl Config {
         CONFIG_RAFTSTORE_GAUGE
             .with_label_values(&["lock_cf_compact_bytes_threshold"])
             .set(self.lock_cf_compact_bytes_threshold.0 as f64);
+        CONFIG_RAFTSTORE_GAUGE
+            .with_label_values(&["lock_cf_compact_tombstones_threshold"])
+            .set(self.lock_cf_compact_tombstones_threshold as f64);
 
         CONFIG_RAFTSTORE_GAUGE
             .with_label_values(&["notify_capacity"])
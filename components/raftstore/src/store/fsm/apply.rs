@@ -385,6 +385,9 @@ where
     perf_context: EK::PerfContext,
 
     yield_duration: Duration,
+    /// Writes merged into `kv_wb` are forced to be written once it grows
+    /// beyond this many bytes.
+    apply_max_wb_bytes: u64,
 
     store_id: u64,
     /// region_id -> (peer_id, is_splitting)
@@ -455,6 +458,7 @@ where
             use_delete_range: cfg.use_delete_range,
             perf_context: engine.get_perf_context(cfg.perf_level, PerfContextKind::RaftstoreApply),
             yield_duration: cfg.apply_yield_duration.0,
+            apply_max_wb_bytes: cfg.apply_max_wb_bytes.0,
             delete_ssts: vec![],
             store_id,
             pending_create_peers,
@@ -1052,6 +1056,7 @@ where
                 self.last_flush_applied_index != self.apply_state.get_applied_index();
             if has_unflushed_data && should_write_to_engine(&cmd)
                 || apply_ctx.kv_wb().should_write_to_engine()
+                || apply_ctx.kv_wb().data_size() as u64 >= apply_ctx.apply_max_wb_bytes
             {
                 apply_ctx.commit(self);
                 if let Some(start) = self.handle_start.as_ref() {
@@ -64,8 +64,8 @@ use crate::store::worker::{
 };
 use crate::store::PdTask;
 use crate::store::{
-    util, AbstractPeer, CasualMessage, Config, MergeResultKind, PeerMsg, PeerTicks, RaftCommand,
-    SignificantMsg, SnapKey, StoreMsg,
+    util, AbstractPeer, Bucket, CasualMessage, Config, MergeResultKind, PeerMsg, PeerTicks,
+    RaftCommand, SignificantMsg, SnapKey, StoreMsg,
 };
 use crate::{Error, Result};
 use keys::{self, enc_end_key, enc_start_key};
@@ -687,6 +687,12 @@ where
             CasualMessage::RegionApproximateKeys { keys } => {
                 self.on_approximate_region_keys(keys);
             }
+            CasualMessage::RefreshRegionBuckets {
+                region_epoch,
+                buckets,
+            } => {
+                self.on_refresh_region_buckets(region_epoch, buckets);
+            }
             CasualMessage::CompactionDeclinedBytes { bytes } => {
                 self.on_compaction_declined_bytes(bytes);
             }
@@ -884,6 +890,7 @@ where
         self.fsm.peer.approximate_size = 0;
         self.fsm.peer.approximate_keys = 0;
         self.fsm.peer.has_calculated_region_size = false;
+        self.fsm.peer.region_buckets = vec![];
         self.register_split_region_check_tick();
     }
 
@@ -3924,6 +3931,15 @@ where
         self.register_pd_heartbeat_tick();
     }
 
+    fn on_refresh_region_buckets(&mut self, region_epoch: RegionEpoch, buckets: Vec<Bucket>) {
+        if util::is_epoch_stale(&region_epoch, self.fsm.peer.region().get_region_epoch()) {
+            // The buckets were collected from a stale range, wait for the next
+            // split check round.
+            return;
+        }
+        self.fsm.peer.region_buckets = buckets;
+    }
+
     fn on_compaction_declined_bytes(&mut self, declined_bytes: u64) {
         self.fsm.peer.compaction_declined_bytes += declined_bytes;
         if self.fsm.peer.compaction_declined_bytes >= self.ctx.cfg.region_split_check_diff.0 {
@@ -4401,6 +4417,9 @@ impl<EK: KvEngine, ER: RaftEngine> AbstractPeer for PeerFsm<EK, ER> {
     fn apply_state(&self) -> &RaftApplyState {
         self.peer.raft_group.store().apply_state()
     }
+    fn applied_index_term(&self) -> u64 {
+        self.peer.raft_group.store().applied_index_term()
+    }
     fn raft_status(&self) -> raft::Status {
         self.peer.raft_group.status()
     }
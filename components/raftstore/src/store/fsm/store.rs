@@ -68,13 +68,15 @@ use crate::store::fsm::{
 use crate::store::local_metrics::RaftMetrics;
 use crate::store::memory::*;
 use crate::store::metrics::*;
+use crate::store::msg_drop::record_dropped_message;
 use crate::store::peer_storage::{self, HandleRaftReadyContext};
 use crate::store::transport::Transport;
 use crate::store::util::{is_initial_msg, RegionReadProgressRegistry};
 use crate::store::worker::{
-    AutoSplitController, CleanupRunner, CleanupSSTRunner, CleanupSSTTask, CleanupTask,
-    CompactRunner, CompactTask, ConsistencyCheckRunner, ConsistencyCheckTask, PdRunner,
-    RaftlogGcRunner, RaftlogGcTask, ReadDelegate, RegionRunner, RegionTask, SplitCheckTask,
+    lock_cf_tombstones_scanned, sub_lock_cf_tombstones_scanned, AutoSplitController,
+    CleanupRunner, CleanupSSTRunner, CleanupSSTTask, CleanupTask, CompactRunner, CompactTask,
+    ConsistencyCheckRunner, ConsistencyCheckTask, PdRunner, RaftlogGcRunner, RaftlogGcTask,
+    ReadDelegate, RegionRunner, RegionTask, SplitCheckTask,
 };
 use crate::store::{
     util, Callback, CasualMessage, GlobalReplicationState, InspectedRaftMessage, MergeResultKind,
@@ -502,6 +504,7 @@ where
         );
 
         self.raft_metrics.message_dropped.stale_msg += 1;
+        record_dropped_message(msg, RaftDroppedMessage::stale_msg);
 
         let mut gc_msg = RaftMessage::default();
         gc_msg.set_region_id(region_id);
@@ -1493,6 +1496,7 @@ impl<'a, EK: KvEngine, ER: RaftEngine, T: Transport> StoreFsmDelegate<'a, EK, ER
             // Maybe split, but not registered yet.
             if !util::is_first_message(msg.get_message()) {
                 self.ctx.raft_metrics.message_dropped.region_nonexistent += 1;
+                record_dropped_message(msg, RaftDroppedMessage::region_nonexistent);
                 return Err(box_err!(
                     "[region {}] region not exist but not tombstone: {:?}",
                     region_id,
@@ -1539,6 +1543,7 @@ impl<'a, EK: KvEngine, ER: RaftEngine, T: Transport> StoreFsmDelegate<'a, EK, ER
         // The region in this peer is already destroyed
         if util::is_epoch_stale(from_epoch, region_epoch) {
             self.ctx.raft_metrics.message_dropped.region_tombstone_peer += 1;
+            record_dropped_message(msg, RaftDroppedMessage::region_tombstone_peer);
             info!(
                 "tombstone peer receives a stale message";
                 "region_id" => region_id,
@@ -1587,6 +1592,7 @@ impl<'a, EK: KvEngine, ER: RaftEngine, T: Transport> StoreFsmDelegate<'a, EK, ER
         {
             if to_peer_id <= local_peer_id {
                 self.ctx.raft_metrics.message_dropped.region_tombstone_peer += 1;
+                record_dropped_message(msg, RaftDroppedMessage::region_tombstone_peer);
                 info!(
                     "tombstone peer receives a stale message, local_peer_id >= to_peer_id in msg";
                     "region_id" => region_id,
@@ -1635,6 +1641,7 @@ impl<'a, EK: KvEngine, ER: RaftEngine, T: Transport> StoreFsmDelegate<'a, EK, ER
                 "region_id" => region_id,
             );
             self.ctx.raft_metrics.message_dropped.mismatch_store_id += 1;
+            record_dropped_message(&msg, RaftDroppedMessage::mismatch_store_id);
             return Ok(());
         }
 
@@ -1644,6 +1651,7 @@ impl<'a, EK: KvEngine, ER: RaftEngine, T: Transport> StoreFsmDelegate<'a, EK, ER
                 "region_id" => region_id,
             );
             self.ctx.raft_metrics.message_dropped.mismatch_region_epoch += 1;
+            record_dropped_message(&msg, RaftDroppedMessage::mismatch_region_epoch);
             return Ok(());
         }
         if msg.get_is_tombstone() || msg.has_merge_target() {
@@ -1714,6 +1722,7 @@ impl<'a, EK: KvEngine, ER: RaftEngine, T: Transport> StoreFsmDelegate<'a, EK, ER
                 "msg_type" => ?msg_type,
             );
             self.ctx.raft_metrics.message_dropped.stale_msg += 1;
+            record_dropped_message(msg, RaftDroppedMessage::stale_msg);
             return Ok(false);
         }
 
@@ -1831,6 +1840,7 @@ impl<'a, EK: KvEngine, ER: RaftEngine, T: Transport> StoreFsmDelegate<'a, EK, ER
 
         if is_overlapped {
             self.ctx.raft_metrics.message_dropped.region_overlap += 1;
+            record_dropped_message(msg, RaftDroppedMessage::region_overlap);
             return Ok(false);
         }
 
@@ -1997,6 +2007,7 @@ impl<'a, EK: KvEngine, ER: RaftEngine, T: Transport> StoreFsmDelegate<'a, EK, ER
                 ranges: ranges_need_check,
                 tombstones_num_threshold: self.ctx.cfg.region_compact_min_tombstones,
                 tombstones_percent_threshold: self.ctx.cfg.region_compact_tombstones_percent,
+                range_tombstones_num_threshold: self.ctx.cfg.region_compact_min_range_tombstones,
             },
         )) {
             error!(
@@ -2167,6 +2178,12 @@ impl<'a, EK: KvEngine, ER: RaftEngine, T: Transport> StoreFsmDelegate<'a, EK, ER
         self.register_snap_mgr_gc_tick();
     }
 
+    // Large transactions leave wide swaths of lock cf tombstones behind, which
+    // slow down every later lock scan until they are compacted away. Two
+    // triggers cover that: a write-volume one (enough bytes went through the
+    // lock cf) and a read-cost one (lock scans skipped enough tombstones, as
+    // reported by the storage read path). Either one compacts the whole lock
+    // cf, which is small enough for that to be cheap.
     fn on_compact_lock_cf(&mut self) {
         // Create a compact lock cf task(compact whole range) and schedule directly.
         let lock_cf_bytes_written = self
@@ -2175,12 +2192,16 @@ impl<'a, EK: KvEngine, ER: RaftEngine, T: Transport> StoreFsmDelegate<'a, EK, ER
             .stat
             .lock_cf_bytes_written
             .load(Ordering::SeqCst);
-        if lock_cf_bytes_written > self.ctx.cfg.lock_cf_compact_bytes_threshold.0 {
+        let tombstones_scanned = lock_cf_tombstones_scanned();
+        if lock_cf_bytes_written > self.ctx.cfg.lock_cf_compact_bytes_threshold.0
+            || tombstones_scanned > self.ctx.cfg.lock_cf_compact_tombstones_threshold
+        {
             self.ctx
                 .global_stat
                 .stat
                 .lock_cf_bytes_written
                 .fetch_sub(lock_cf_bytes_written, Ordering::SeqCst);
+            sub_lock_cf_tombstones_scanned(tombstones_scanned);
 
             let task = CompactTask::Compact {
                 cf_name: String::from(CF_LOCK),
@@ -558,6 +558,19 @@ lazy_static! {
             "Pending read index count."
         ).unwrap();
 
+    pub static ref RAFT_READ_INDEX_RETRY_COUNTER: IntCounter =
+        register_int_counter!(
+            "tikv_raftstore_read_index_retry_total",
+            "Total number of read index requests retried because the response may be lost."
+        ).unwrap();
+
+    pub static ref TRANSFER_LEADER_REJECT_COUNTER_VEC: IntCounterVec =
+        register_int_counter_vec!(
+            "tikv_raftstore_transfer_leader_reject_total",
+            "Total number of rejected leader transfers by reason.",
+            &["reason"]
+        ).unwrap();
+
     pub static ref APPLY_PERF_CONTEXT_TIME_HISTOGRAM: HistogramVec =
         register_histogram_vec!(
             "tikv_raftstore_apply_perf_context_time_duration_secs",
@@ -16,6 +16,7 @@ mod compaction_guard;
 mod hibernate_state;
 mod local_metrics;
 mod metrics;
+mod msg_drop;
 mod peer;
 mod peer_storage;
 mod read_queue;
@@ -34,10 +35,11 @@ pub use self::fsm::{DestroyPeerJob, RaftRouter, StoreInfo};
 pub use self::hibernate_state::{GroupState, HibernateState};
 pub use self::memory::*;
 pub use self::msg::{
-    Callback, CasualMessage, ExtCallback, InspectedRaftMessage, MergeResultKind, PeerMsg,
+    Bucket, Callback, CasualMessage, ExtCallback, InspectedRaftMessage, MergeResultKind, PeerMsg,
     PeerTicks, RaftCmdExtraOpts, RaftCommand, ReadCallback, ReadResponse, SignificantMsg, StoreMsg,
     StoreTick, WriteCallback, WriteResponse,
 };
+pub use self::msg_drop::{dump_dropped_messages, DroppedMessage};
 pub use self::peer::{
     AbstractPeer, Peer, PeerStat, ProposalContext, RequestInspector, RequestPolicy,
 };
@@ -61,6 +63,9 @@ pub use self::worker::{
     AutoSplitController, FlowStatistics, FlowStatsReporter, PdTask, QueryStats, ReadDelegate,
     ReadStats, SplitConfig, SplitConfigManager, TrackVer, WriteStats,
 };
+pub use self::worker::{
+    add_lock_cf_tombstones_scanned, lock_cf_tombstones_scanned, sub_lock_cf_tombstones_scanned,
+};
 pub use self::worker::{CheckLeaderRunner, CheckLeaderTask};
 pub use self::worker::{KeyEntry, LocalReader, RegionTask};
 pub use self::worker::{SplitCheckRunner, SplitCheckTask};
@@ -293,6 +293,18 @@ where
     LeaderCallback(Callback<SK>),
 }
 
+/// Size statistics of a sub-range of a region, named bucket. Buckets divide
+/// a big region into roughly even ranges so that it doesn't have to be treated
+/// as one opaque unit by diagnostics.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Bucket {
+    /// The start key of the bucket. The bucket ends at the start key of the
+    /// next bucket, or at the end key of the region for the last one.
+    pub start_key: Vec<u8>,
+    pub size: u64,
+    pub keys: u64,
+}
+
 /// Message that will be sent to a peer.
 ///
 /// These messages are not significant and can be dropped occasionally.
@@ -323,6 +335,12 @@ pub enum CasualMessage<EK: KvEngine> {
     RegionApproximateKeys {
         keys: u64,
     },
+    /// Buckets of target region collected by a full split check scan. This
+    /// message can only be sent by split-check thread.
+    RefreshRegionBuckets {
+        region_epoch: RegionEpoch,
+        buckets: Vec<Bucket>,
+    },
     CompactionDeclinedBytes {
         bytes: u64,
     },
@@ -392,6 +410,9 @@ impl<EK: KvEngine> fmt::Debug for CasualMessage<EK> {
             CasualMessage::RegionApproximateKeys { keys } => {
                 write!(fmt, "Region's approximate keys [keys: {:?}]", keys)
             }
+            CasualMessage::RefreshRegionBuckets { buckets, .. } => {
+                write!(fmt, "Region's buckets [count: {}]", buckets.len())
+            }
             CasualMessage::CompactionDeclinedBytes { bytes } => {
                 write!(fmt, "compaction declined bytes {}", bytes)
             }
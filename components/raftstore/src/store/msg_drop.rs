@@ -0,0 +1,85 @@
+// Copyright 2021 TiKV Project Authors. Licensed under Apache-2.0.
+
+//! A bounded in-memory log of recently dropped raft messages.
+//!
+//! `tikv_raftstore_raft_dropped_message_total` tells how many messages are
+//! dropped for each reason, but investigating a concrete stuck region needs
+//! to know which messages were dropped. The recorder below keeps the most
+//! recent store-level drops, i.e. messages discarded before they reach a
+//! peer, together with their region and peer ids, so they can be inspected
+//! through the status server.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use kvproto::raft_serverpb::RaftMessage;
+use lazy_static::lazy_static;
+use serde::Serialize;
+
+use crate::store::metrics::RaftDroppedMessage;
+
+/// How many recent drops are kept. Drops can be frequent when a store is
+/// really unhealthy, so the buffer is small and old records are discarded.
+const CAPACITY: usize = 256;
+
+#[derive(Clone, Serialize)]
+pub struct DroppedMessage {
+    pub region_id: u64,
+    pub from_peer_id: u64,
+    pub to_peer_id: u64,
+    pub msg_type: String,
+    pub reason: &'static str,
+    /// Unix timestamp in seconds at which the message was dropped.
+    pub drop_time: u64,
+}
+
+lazy_static! {
+    static ref RECENT_DROPS: Mutex<VecDeque<DroppedMessage>> =
+        Mutex::new(VecDeque::with_capacity(CAPACITY));
+}
+
+/// Records a dropped raft message. It must not block a raftstore thread, so
+/// if the buffer is contended the record is skipped; the counters in
+/// `message_dropped` stay accurate either way.
+pub fn record_dropped_message(msg: &RaftMessage, reason: RaftDroppedMessage) {
+    let record = DroppedMessage {
+        region_id: msg.get_region_id(),
+        from_peer_id: msg.get_from_peer().get_id(),
+        to_peer_id: msg.get_to_peer().get_id(),
+        msg_type: format!("{:?}", msg.get_message().get_msg_type()),
+        reason: reason.get_str(),
+        drop_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+    };
+    if let Ok(mut buf) = RECENT_DROPS.try_lock() {
+        if buf.len() >= CAPACITY {
+            buf.pop_front();
+        }
+        buf.push_back(record);
+    }
+}
+
+/// Returns the recently dropped messages, oldest first.
+pub fn dump_dropped_messages() -> Vec<DroppedMessage> {
+    RECENT_DROPS.lock().unwrap().iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorder_is_bounded() {
+        let mut msg = RaftMessage::default();
+        msg.set_region_id(1);
+        for _ in 0..CAPACITY + 10 {
+            record_dropped_message(&msg, RaftDroppedMessage::stale_msg);
+        }
+        let dump = dump_dropped_messages();
+        assert_eq!(dump.len(), CAPACITY);
+        assert_eq!(dump[0].region_id, 1);
+        assert_eq!(dump[0].reason, "stale_msg");
+    }
+}
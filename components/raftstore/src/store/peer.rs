@@ -50,7 +50,7 @@ use crate::store::msg::RaftCommand;
 use crate::store::util::{admin_cmd_epoch_lookup, RegionReadProgress};
 use crate::store::worker::{HeartbeatTask, ReadDelegate, ReadExecutor, ReadProgress, RegionTask};
 use crate::store::{
-    Callback, Config, GlobalReplicationState, PdTask, ReadIndexContext, ReadResponse,
+    Bucket, Callback, Config, GlobalReplicationState, PdTask, ReadIndexContext, ReadResponse,
 };
 use crate::{Error, Result};
 use collections::{HashMap, HashSet};
@@ -450,6 +450,10 @@ where
     ///  the region or ingested one file which may be overlapped with the existed data, the
     /// `approximate_size` is not very accurate.
     pub has_calculated_region_size: bool,
+    /// Buckets of the region collected by the latest full split check scan.
+    /// They stay local for hot spot diagnostics; reporting them to PD needs
+    /// protocol support that doesn't exist yet.
+    pub region_buckets: Vec<Bucket>,
 
     /// The state for consistency check.
     pub consistency_state: ConsistencyState,
@@ -582,6 +586,7 @@ where
             approximate_size: 0,
             approximate_keys: 0,
             has_calculated_region_size: false,
+            region_buckets: vec![],
             compaction_declined_bytes: 0,
             leader_unreachable: false,
             pending_remove: false,
@@ -1395,6 +1400,24 @@ where
                             "region_id" => self.region_id,
                         );
                     }
+
+                    if ctx.cfg.prefetch_region_on_become_leader {
+                        // Warm up the block cache so the first wave of reads
+                        // after the leader transfer doesn't hit cold disk.
+                        let task = RegionTask::Prefetch {
+                            region_id: self.region_id,
+                            start_key: keys::enc_start_key(self.region()),
+                            end_key: keys::enc_end_key(self.region()),
+                        };
+                        if let Err(e) = ctx.region_scheduler.schedule(task) {
+                            error!(
+                                "failed to schedule prefetch task";
+                                "region_id" => self.region_id,
+                                "peer_id" => self.peer.get_id(),
+                                "err" => %e,
+                            );
+                        }
+                    }
                 }
                 StateRole::Follower => {
                     self.leader_lease.expire();
@@ -1551,6 +1574,16 @@ where
         self.last_committed_split_idx > self.get_store().applied_index()
     }
 
+    /// Whether the region is in the middle of a merge, in which case local
+    /// reads must fall back to read index.
+    ///
+    /// Lease reads stay correct across a merge because both halves give up
+    /// their leases before the range changes hands: the source suspects its
+    /// lease as soon as PrepareMerge is committed (see
+    /// `on_leader_commit_idx_changed`) and rejects reads here until the merge
+    /// settles, while the target only serves the extended range after
+    /// CommitMerge applies, which bumps the epoch version and expires the
+    /// remote lease of the local reader (see `set_region`).
     #[inline]
     fn is_merging(&self) -> bool {
         self.last_committed_prepare_merge_idx > self.get_store().applied_index()
@@ -2204,6 +2237,9 @@ where
         // Reset delete_keys_hint and size_diff_hint.
         self.delete_keys_hint = 0;
         self.size_diff_hint = 0;
+        // The buckets are collected from the range before the split, so they
+        // don't match the derived region anymore.
+        self.region_buckets = vec![];
     }
 
     /// Try to renew leader lease.
@@ -2584,12 +2620,12 @@ where
         let progress = status.progress.unwrap();
 
         if !progress.conf().voters().contains(peer_id) {
-            return Some("non voter");
+            return Some("non_voter");
         }
 
         for (id, pr) in progress.iter() {
             if pr.state == ProgressState::Snapshot {
-                return Some("pending snapshot");
+                return Some("pending_snapshot");
             }
             if *id == peer_id && index == 0 {
                 // index will be zero if it's sent from an instance without
@@ -2603,12 +2639,12 @@ where
         if self.raft_group.raft.has_pending_conf()
             || self.raft_group.raft.pending_conf_index > index
         {
-            return Some("pending conf change");
+            return Some("pending_conf_change");
         }
 
         let last_index = self.get_store().last_index();
         if last_index >= index + ctx.cfg.leader_transfer_max_log_lag {
-            return Some("log gap");
+            return Some("log_gap");
         }
         None
     }
@@ -2674,6 +2710,7 @@ where
                 read.addition_request.as_deref(),
                 None,
             ));
+        RAFT_READ_INDEX_RETRY_COUNTER.inc();
         debug!(
             "request to get a read index";
             "request_id" => ?read.id,
@@ -3049,6 +3086,12 @@ where
         // TODO: use local histogram metrics
         PEER_PROPOSE_LOG_SIZE_HISTOGRAM.observe(data.len() as f64);
 
+        // An oversized write is rejected with a dedicated error instead of
+        // being split: raft applies entry by entry, so splitting one command
+        // over several entries would break its atomicity whenever a restart
+        // or a leader change lands between them. Clients see
+        // `RaftEntryTooLarge` with the region and size and have to cut the
+        // batch themselves, where transactionality is handled properly.
         if data.len() as u64 > poll_ctx.cfg.raft_entry_max_size.0 {
             error!(
                 "entry is too large";
@@ -3091,6 +3134,9 @@ where
             };
             match self.ready_to_transfer_leader(ctx, msg.get_index(), &from) {
                 Some(reason) => {
+                    TRANSFER_LEADER_REJECT_COUNTER_VEC
+                        .with_label_values(&[reason])
+                        .inc();
                     info!(
                         "reject to transfer leader";
                         "region_id" => self.region_id,
@@ -3418,6 +3464,16 @@ where
 
     // Check disk usages for the peer itself and other peers in the raft group.
     // The return value indicates whether the proposal is allowed or not.
+    //
+    // This implements the degraded mode on low disk space: ordinary client
+    // writes carry `NotAllowedOnFull` and are rejected once the disk is
+    // almost full, while space-freeing proposals (log compaction, tombstone
+    // cleanup, conf changes removing the peer) carry `AllowedOnAlmostFull`
+    // and keep working. Raft messages and applying committed entries are
+    // never blocked here. When the disk is `AlreadyFull` even the privileged
+    // proposals stop: `DiskFullOpt` only distinguishes "not allowed on full"
+    // from "allowed on almost full", so there is no way for a proposal to
+    // declare itself safe to run on a completely full disk.
     fn check_disk_usages_before_propose<T>(
         &mut self,
         ctx: &mut PollContext<EK, ER, T>,
@@ -3982,6 +4038,7 @@ pub trait AbstractPeer {
     fn group_state(&self) -> GroupState;
     fn region(&self) -> &metapb::Region;
     fn apply_state(&self) -> &RaftApplyState;
+    fn applied_index_term(&self) -> u64;
     fn raft_status(&self) -> raft::Status;
     fn raft_commit_index(&self) -> u64;
     fn pending_merge_state(&self) -> Option<&MergeState>;
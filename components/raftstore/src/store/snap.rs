@@ -79,6 +79,9 @@ pub enum Error {
     #[error("too many snapshots")]
     TooManySnapshots,
 
+    #[error("not enough disk space, available {available}, required {required}")]
+    NoSpace { available: u64, required: u64 },
+
     #[error("snap failed {0:?}")]
     Other(#[from] Box<dyn StdError + Sync + Send>),
 }
@@ -96,6 +99,7 @@ impl ErrorCodeExt for Error {
         match self {
             Error::Abort => error_code::raftstore::SNAP_ABORT,
             Error::TooManySnapshots => error_code::raftstore::SNAP_TOO_MANY,
+            Error::NoSpace { .. } => error_code::raftstore::SNAP_NO_SPACE,
             Error::Other(_) => error_code::raftstore::SNAP_UNKNOWN,
         }
     }
@@ -1281,6 +1285,19 @@ impl SnapManager {
         snapshot_data.merge_from_bytes(data)?;
         let base = &self.core.base;
         let f = Snapshot::new_for_receiving(base, key, &self.core, snapshot_data.take_meta())?;
+        // The total size is known from the very first chunk, so a snapshot the
+        // disk can't hold is rejected before its data is transferred.
+        if !f.exists() {
+            let required = f.total_size()?;
+            let disk_stats = fs2::statvfs(base)?;
+            let available = disk_stats.available_space();
+            if available < required {
+                return Err(RaftStoreError::Snapshot(Error::NoSpace {
+                    available,
+                    required,
+                }));
+            }
+        }
         Ok(Box::new(f))
     }
 
@@ -3,6 +3,7 @@
 use std::collections::VecDeque;
 use std::error::Error as StdError;
 use std::fmt::{self, Display, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use fail::fail_point;
 use thiserror::Error;
@@ -13,10 +14,35 @@ use tikv_util::time::Instant;
 use tikv_util::worker::Runnable;
 use tikv_util::{box_try, error, info, warn};
 
-use super::metrics::COMPACT_RANGE_CF;
+use super::metrics::{COMPACT_RANGE_CF, RANGE_TOMBSTONE_GAUGE_VEC};
 
 type Key = Vec<u8>;
 
+// Lock CF tombstones traversed by storage reads (`scan_lock`, the lock checks
+// of transactional writes) since the last lock CF compaction. Large
+// transactions leave wide swaths of lock CF tombstones behind that slow down
+// every later scan; the storage layer feeds its traversal statistics in here
+// and the store's compact-lock-cf tick compacts the CF once enough of them
+// accumulate, without waiting for the write-volume trigger.
+static LOCK_CF_TOMBSTONES_SCANNED: AtomicU64 = AtomicU64::new(0);
+
+/// Adds `n` traversed lock CF tombstones to the compaction trigger statistics.
+pub fn add_lock_cf_tombstones_scanned(n: u64) {
+    LOCK_CF_TOMBSTONES_SCANNED.fetch_add(n, Ordering::Relaxed);
+}
+
+/// Returns the number of lock CF tombstones traversed since the last
+/// subtraction.
+pub fn lock_cf_tombstones_scanned() -> u64 {
+    LOCK_CF_TOMBSTONES_SCANNED.load(Ordering::Relaxed)
+}
+
+/// Subtracts tombstones that have been accounted for by a scheduled
+/// compaction.
+pub fn sub_lock_cf_tombstones_scanned(n: u64) {
+    LOCK_CF_TOMBSTONES_SCANNED.fetch_sub(n, Ordering::Relaxed);
+}
+
 pub enum Task {
     Compact {
         cf_name: String,
@@ -29,6 +55,7 @@ pub enum Task {
         ranges: Vec<Key>,              // Ranges need to check
         tombstones_num_threshold: u64, // The minimum RocksDB tombstones a range that need compacting has
         tombstones_percent_threshold: u64,
+        range_tombstones_num_threshold: u64, // The minimum DeleteRange tombstones, checked per cf
     },
 }
 
@@ -56,6 +83,7 @@ impl Display for Task {
                 ref ranges,
                 tombstones_num_threshold,
                 tombstones_percent_threshold,
+                range_tombstones_num_threshold,
             } => f
                 .debug_struct("CheckAndCompact")
                 .field("cf_names", cf_names)
@@ -71,6 +99,10 @@ impl Display for Task {
                     "tombstones_percent_threshold",
                     &tombstones_percent_threshold,
                 )
+                .field(
+                    "range_tombstones_num_threshold",
+                    &range_tombstones_num_threshold,
+                )
                 .finish(),
         }
     }
@@ -145,34 +177,70 @@ where
                 ranges,
                 tombstones_num_threshold,
                 tombstones_percent_threshold,
-            } => match collect_ranges_need_compact(
-                &self.engine,
-                ranges,
-                tombstones_num_threshold,
-                tombstones_percent_threshold,
-            ) {
-                Ok(mut ranges) => {
-                    for (start, end) in ranges.drain(..) {
-                        for cf in &cf_names {
-                            if let Err(e) = self.compact_range_cf(cf, Some(&start), Some(&end)) {
-                                error!(
-                                    "compact range failed";
-                                    "range_start" => log_wrappers::Value::key(&start),
-                                    "range_end" => log_wrappers::Value::key(&end),
-                                    "cf" => cf,
-                                    "err" => %e,
-                                );
+                range_tombstones_num_threshold,
+            } => {
+                match collect_ranges_need_compact(
+                    &self.engine,
+                    &ranges,
+                    tombstones_num_threshold,
+                    tombstones_percent_threshold,
+                ) {
+                    Ok(mut ranges) => {
+                        for (start, end) in ranges.drain(..) {
+                            for cf in &cf_names {
+                                if let Err(e) = self.compact_range_cf(cf, Some(&start), Some(&end))
+                                {
+                                    error!(
+                                        "compact range failed";
+                                        "range_start" => log_wrappers::Value::key(&start),
+                                        "range_end" => log_wrappers::Value::key(&end),
+                                        "cf" => cf,
+                                        "err" => %e,
+                                    );
+                                }
+                            }
+                            fail_point!("raftstore::compact::CheckAndCompact:AfterCompact");
+                        }
+                    }
+                    Err(e) => warn!("check ranges need reclaim failed"; "err" => %e),
+                }
+                for cf in &cf_names {
+                    match collect_ranges_with_range_tombstones(
+                        &self.engine,
+                        cf,
+                        &ranges,
+                        range_tombstones_num_threshold,
+                    ) {
+                        Ok((num_range_tombstones, mut ranges)) => {
+                            RANGE_TOMBSTONE_GAUGE_VEC
+                                .with_label_values(&[cf])
+                                .set(num_range_tombstones as i64);
+                            for (start, end) in ranges.drain(..) {
+                                if let Err(e) = self.compact_range_cf(cf, Some(&start), Some(&end))
+                                {
+                                    error!(
+                                        "compact range tombstones failed";
+                                        "range_start" => log_wrappers::Value::key(&start),
+                                        "range_end" => log_wrappers::Value::key(&end),
+                                        "cf" => cf,
+                                        "err" => %e,
+                                    );
+                                }
                             }
                         }
-                        fail_point!("raftstore::compact::CheckAndCompact:AfterCompact");
+                        Err(e) => {
+                            warn!("check ranges with range tombstones failed"; "cf" => cf, "err" => %e)
+                        }
                     }
                 }
-                Err(e) => warn!("check ranges need reclaim failed"; "err" => %e),
-            },
+            }
         }
     }
 }
 
+// The estimate below only sees point tombstones, derived from the MVCC table
+// properties; range tombstones are checked separately by
+// `collect_ranges_with_range_tombstones`.
 fn need_compact(
     num_entires: u64,
     num_versions: u64,
@@ -191,7 +259,7 @@ fn need_compact(
 
 fn collect_ranges_need_compact(
     engine: &impl KvEngine,
-    ranges: Vec<Key>,
+    ranges: &[Key],
     tombstones_num_threshold: u64,
     tombstones_percent_threshold: u64,
 ) -> Result<VecDeque<(Key, Key)>, Error> {
@@ -244,6 +312,52 @@ fn collect_ranges_need_compact(
     Ok(ranges_need_compact)
 }
 
+// Range tombstones left by DeleteRange are invisible to the MVCC estimate in
+// `collect_ranges_need_compact`: each one is a single entry that may shadow an
+// arbitrarily wide swath of keys, so they are counted from their own table
+// property, per cf, and a much lower threshold applies. Besides the ranges
+// that need compacting, the total number of range tombstones seen in the
+// checked ranges is returned for the metrics.
+fn collect_ranges_with_range_tombstones(
+    engine: &impl KvEngine,
+    cf: &str,
+    ranges: &[Key],
+    range_tombstones_num_threshold: u64,
+) -> Result<(u64, VecDeque<(Key, Key)>), Error> {
+    let mut num_range_tombstones = 0;
+    let mut ranges_need_compact = VecDeque::new();
+    let mut compact_start = None;
+    let mut compact_end = None;
+    for range in ranges.windows(2) {
+        if let Some(num) =
+            box_try!(engine.get_range_num_range_deletions(cf, &range[0], &range[1]))
+        {
+            num_range_tombstones += num;
+            if num >= range_tombstones_num_threshold {
+                if compact_start.is_none() {
+                    // The previous range doesn't need compacting.
+                    compact_start = Some(range[0].clone());
+                }
+                compact_end = Some(range[1].clone());
+                // Move to next range.
+                continue;
+            }
+        }
+
+        // Current range doesn't need compacting, save previous range that need compacting.
+        if let (Some(cs), Some(ce)) = (compact_start.take(), compact_end.take()) {
+            ranges_need_compact.push_back((cs, ce));
+        }
+    }
+
+    // Save the last range that needs to be compacted.
+    if let (Some(cs), Some(ce)) = (compact_start, compact_end) {
+        ranges_need_compact.push_back((cs, ce));
+    }
+
+    Ok((num_range_tombstones, ranges_need_compact))
+}
+
 #[cfg(test)]
 mod tests {
     use std::thread::sleep;
@@ -252,7 +366,9 @@ mod tests {
     use engine_test::ctor::{CFOptions, ColumnFamilyOptions, DBOptions};
     use engine_test::kv::KvTestEngine;
     use engine_test::kv::{new_engine, new_engine_opt};
-    use engine_traits::{MiscExt, Mutable, SyncMutable, WriteBatch, WriteBatchExt};
+    use engine_traits::{
+        DeleteStrategy, MiscExt, Mutable, Range, SyncMutable, WriteBatch, WriteBatchExt,
+    };
     use engine_traits::{CF_DEFAULT, CF_LOCK, CF_RAFT, CF_WRITE};
     use tempfile::Builder;
 
@@ -376,7 +492,7 @@ mod tests {
 
         let ranges_need_to_compact = collect_ranges_need_compact(
             &engine,
-            vec![data_key(b"k0"), data_key(b"k5"), data_key(b"k9")],
+            &[data_key(b"k0"), data_key(b"k5"), data_key(b"k9")],
             1,
             50,
         )
@@ -403,7 +519,7 @@ mod tests {
 
         let ranges_need_to_compact = collect_ranges_need_compact(
             &engine,
-            vec![data_key(b"k0"), data_key(b"k5"), data_key(b"k9")],
+            &[data_key(b"k0"), data_key(b"k5"), data_key(b"k9")],
             1,
             50,
         )
@@ -413,4 +529,48 @@ mod tests {
         expected_ranges.push_back((s, e));
         assert_eq!(ranges_need_to_compact, expected_ranges);
     }
+
+    #[test]
+    fn test_check_range_tombstones() {
+        let tmp_dir = Builder::new().prefix("test").tempdir().unwrap();
+        let engine = open_db(tmp_dir.path().to_str().unwrap());
+
+        for i in 0..10 {
+            let (k, v) = (format!("k{}", i), format!("value{}", i));
+            engine
+                .put_cf(CF_DEFAULT, &data_key(k.as_bytes()), v.as_bytes())
+                .unwrap();
+        }
+        engine.flush_cf(CF_DEFAULT, true).unwrap();
+
+        let (start, end) = (data_key(b"k0"), data_key(b"k9"));
+        let ranges_to_check = [start.clone(), end.clone()];
+        let (num, ranges) =
+            collect_ranges_with_range_tombstones(&engine, CF_DEFAULT, &ranges_to_check, 1).unwrap();
+        assert_eq!(num, 0);
+        assert!(ranges.is_empty());
+
+        // Shadow half of the keys with a range tombstone and flush it into an SST.
+        engine
+            .delete_ranges_cf(
+                CF_DEFAULT,
+                DeleteStrategy::DeleteByRange,
+                &[Range::new(&data_key(b"k0"), &data_key(b"k5"))],
+            )
+            .unwrap();
+        engine.flush_cf(CF_DEFAULT, true).unwrap();
+
+        let (num, ranges) =
+            collect_ranges_with_range_tombstones(&engine, CF_DEFAULT, &ranges_to_check, 1).unwrap();
+        assert_eq!(num, 1);
+        let mut expected_ranges = VecDeque::new();
+        expected_ranges.push_back((start, end));
+        assert_eq!(ranges, expected_ranges);
+
+        // A higher threshold leaves the range alone.
+        let (num, ranges) =
+            collect_ranges_with_range_tombstones(&engine, CF_DEFAULT, &ranges_to_check, 2).unwrap();
+        assert_eq!(num, 1);
+        assert!(ranges.is_empty());
+    }
 }
@@ -89,11 +89,22 @@ lazy_static! {
         &["cf"]
     )
     .unwrap();
+    pub static ref RANGE_TOMBSTONE_GAUGE_VEC: IntGaugeVec = register_int_gauge_vec!(
+        "tikv_raftstore_range_tombstones",
+        "Number of range tombstones observed in the ranges of the last compact check, per cf",
+        &["cf"]
+    )
+    .unwrap();
     pub static ref REGION_HASH_HISTOGRAM: Histogram = register_histogram!(
         "tikv_raftstore_hash_duration_seconds",
         "Bucketed histogram of raftstore hash computation duration"
     )
     .unwrap();
+    pub static ref PENDING_APPLY_GAUGE: IntGauge = register_int_gauge!(
+        "tikv_raftstore_snapshot_pending_applies",
+        "Total number of snapshot applies waiting for admission"
+    )
+    .unwrap();
     pub static ref STALE_PEER_PENDING_DELETE_RANGE_GAUGE: Gauge = register_gauge!(
         "tikv_pending_delete_ranges_of_stale_peer",
         "Total number of tikv pending delete range of stale peer"
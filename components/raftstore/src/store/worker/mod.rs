@@ -18,7 +18,10 @@ mod split_controller;
 pub use self::check_leader::{Runner as CheckLeaderRunner, Task as CheckLeaderTask};
 pub use self::cleanup::{Runner as CleanupRunner, Task as CleanupTask};
 pub use self::cleanup_sst::{Runner as CleanupSSTRunner, Task as CleanupSSTTask};
-pub use self::compact::{Runner as CompactRunner, Task as CompactTask};
+pub use self::compact::{
+    add_lock_cf_tombstones_scanned, lock_cf_tombstones_scanned, sub_lock_cf_tombstones_scanned,
+    Runner as CompactRunner, Task as CompactTask,
+};
 pub use self::consistency_check::{Runner as ConsistencyCheckRunner, Task as ConsistencyCheckTask};
 pub use self::pd::{
     FlowStatistics, FlowStatsReporter, HeartbeatTask, Runner as PdRunner, Task as PdTask,
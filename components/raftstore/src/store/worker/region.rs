@@ -9,7 +9,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use std::u64;
 
-use engine_traits::{DeleteStrategy, Range, CF_LOCK, CF_RAFT};
+use engine_traits::{DeleteStrategy, Range, CF_LOCK, CF_RAFT, DATA_CFS};
 use engine_traits::{KvEngine, Mutable, WriteBatch};
 use fail::fail_point;
 use kvproto::raft_serverpb::{PeerState, RaftApplyState, RegionLocalState};
@@ -77,6 +77,12 @@ pub enum Task<S> {
         start_key: Vec<u8>,
         end_key: Vec<u8>,
     },
+    /// Load data between [start_key, end_key) into the block cache.
+    Prefetch {
+        region_id: u64,
+        start_key: Vec<u8>,
+        end_key: Vec<u8>,
+    },
 }
 
 impl<S> Task<S> {
@@ -105,6 +111,17 @@ impl<S> Display for Task<S> {
                 log_wrappers::Value::key(start_key),
                 log_wrappers::Value::key(end_key)
             ),
+            Task::Prefetch {
+                region_id,
+                ref start_key,
+                ref end_key,
+            } => write!(
+                f,
+                "Prefetch {} [{}, {})",
+                region_id,
+                log_wrappers::Value::key(start_key),
+                log_wrappers::Value::key(end_key)
+            ),
         }
     }
 }
@@ -444,6 +461,26 @@ where
             .observe(start.saturating_elapsed_secs());
     }
 
+    /// Reads the range through once to load it into the block cache, so the
+    /// first reads served by a new leader don't hit cold disk.
+    fn handle_prefetch(&self, region_id: u64, start_key: &[u8], end_key: &[u8]) {
+        let timer = Instant::now();
+        for cf in DATA_CFS {
+            if let Err(e) = self
+                .engine
+                .prefetch_range(cf, &Range::new(start_key, end_key))
+            {
+                error!(%e; "failed to prefetch range"; "region_id" => region_id, "cf" => cf);
+                return;
+            }
+        }
+        info!(
+            "prefetch range";
+            "region_id" => region_id,
+            "time_takes" => ?timer.saturating_elapsed(),
+        );
+    }
+
     /// Cleans up the data within the range.
     fn cleanup_range(&self, ranges: &[Range]) -> Result<()> {
         self.engine
@@ -647,6 +684,13 @@ where
     }
 
     /// Tries to apply pending tasks if there is some.
+    ///
+    /// Tasks are admitted in arrival order, so applies of one region can't be
+    /// reordered, and they run one by one on the worker thread, which bounds
+    /// the apply concurrency of the whole store. A task whose region has been
+    /// destroyed in the meantime is aborted inside `handle_apply` through its
+    /// job status. Per-disk admission limits would only make sense once a
+    /// store can spread regions over multiple kv engines.
     fn handle_pending_applies(&mut self) {
         fail_point!("apply_pending_snapshot", |_| {});
         while !self.pending_applies.is_empty() {
@@ -659,6 +703,7 @@ where
                 self.ctx.handle_apply(region_id, status);
             }
         }
+        PENDING_APPLY_GAUGE.set(self.pending_applies.len() as i64);
     }
 }
 
@@ -720,6 +765,18 @@ where
                     .insert_pending_delete_range(region_id, &start_key, &end_key);
                 self.ctx.clean_stale_ranges();
             }
+            Task::Prefetch {
+                region_id,
+                start_key,
+                end_key,
+            } => {
+                let ctx = self.ctx.clone();
+                self.pool.spawn(async move {
+                    tikv_alloc::add_thread_memory_accessor();
+                    ctx.handle_prefetch(region_id, &start_key, &end_key);
+                    tikv_alloc::remove_thread_memory_accessor();
+                });
+            }
         }
     }
 
@@ -14,7 +14,7 @@ use kvproto::pdpb::CheckPolicy;
 use crate::coprocessor::Config;
 use crate::coprocessor::CoprocessorHost;
 use crate::coprocessor::SplitCheckerHost;
-use crate::store::{Callback, CasualMessage, CasualRouter};
+use crate::store::{Bucket, Callback, CasualMessage, CasualRouter};
 use crate::Result;
 use file_system::{IOType, WithIOType};
 use online_config::{ConfigChange, OnlineConfig};
@@ -277,12 +277,34 @@ where
         .map(|mut iter| {
             let mut size = 0;
             let mut keys = 0;
+            let collect_buckets = self.coprocessor.cfg.enable_region_bucket;
+            let bucket_size_limit = self.coprocessor.cfg.region_bucket_size.0;
+            let mut buckets = vec![];
             while let Some(e) = iter.next() {
                 if host.on_kv(region, &e) {
                     return;
                 }
                 size += e.entry_size() as u64;
                 keys += 1;
+                if collect_buckets {
+                    if buckets
+                        .last()
+                        .map_or(true, |b: &Bucket| b.size >= bucket_size_limit)
+                    {
+                        let start_key = if buckets.is_empty() {
+                            region.get_start_key().to_vec()
+                        } else {
+                            keys::origin_key(e.key()).to_vec()
+                        };
+                        buckets.push(Bucket {
+                            start_key,
+                            ..Default::default()
+                        });
+                    }
+                    let bucket = buckets.last_mut().unwrap();
+                    bucket.size += e.entry_size() as u64;
+                    bucket.keys += 1;
+                }
             }
 
             // if we scan the whole range, we can update approximate size and keys with accurate value.
@@ -300,6 +322,15 @@ where
                 region.get_id(),
                 CasualMessage::RegionApproximateKeys { keys },
             );
+            if collect_buckets {
+                let _ = self.router.send(
+                    region.get_id(),
+                    CasualMessage::RefreshRegionBuckets {
+                        region_epoch: region.get_region_epoch().clone(),
+                        buckets,
+                    },
+                );
+            }
         })?;
         timer.observe_duration();
 
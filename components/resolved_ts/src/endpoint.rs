@@ -778,12 +778,19 @@ where
                 }
             }
         }
+        // No region has advanced its safe ts yet, report zero instead of the
+        // u64::MAX placeholder so the gauges are not rendered as -1.
+        if oldest_ts == u64::MAX {
+            oldest_ts = 0;
+        }
         RTS_MIN_RESOLVED_TS_REGION.set(oldest_region as i64);
         RTS_MIN_RESOLVED_TS.set(oldest_ts as i64);
         RTS_ZERO_RESOLVED_TS.set(zero_ts_count as i64);
-        RTS_MIN_RESOLVED_TS_GAP.set(
-            TimeStamp::physical_now().saturating_sub(TimeStamp::from(oldest_ts).physical()) as i64,
-        );
+        RTS_MIN_RESOLVED_TS_GAP.set(if oldest_ts == 0 {
+            0
+        } else {
+            TimeStamp::physical_now().saturating_sub(TimeStamp::from(oldest_ts).physical()) as i64
+        });
         RTS_LOCK_HEAP_BYTES_GAUGE.set(lock_heap_size as i64);
         RTS_REGION_RESOLVE_STATUS_GAUGE_VEC
             .with_label_values(&["resolved"])
@@ -110,6 +110,9 @@ impl<T: 'static + RaftStoreRouter<E>, E: KvEngine> ScannerPool<T, E> {
                     };
                     let mut scanner = ScannerBuilder::new(snap, TimeStamp::max())
                         .range(None, None)
+                        // Deltas only involve commit ts after the checkpoint, so SST
+                        // files with no version in that range can be skipped.
+                        .hint_min_ts(Some(task.checkpoint_ts.next()))
                         .build_delta_scanner(task.checkpoint_ts, txn_extra_op)
                         .unwrap();
                     let mut done = false;
@@ -190,13 +190,15 @@ impl RunnableWithTimer for ResourceMeteringReporter {
             return;
         }
 
-        let records = std::mem::take(&mut self.records);
-        let others = std::mem::take(&mut self.others);
-
+        // Keep accumulating until the previous report finishes, otherwise the
+        // records of this interval would be silently dropped.
         if self.reporting.load(SeqCst) {
             return;
         }
 
+        let records = std::mem::take(&mut self.records);
+        let others = std::mem::take(&mut self.others);
+
         if let Some(client) = self.client.as_ref() {
             match client.report_cpu_time_opt(CallOption::default().timeout(Duration::from_secs(2)))
             {
@@ -14,19 +14,25 @@ pub struct MemoryTraceManager {
 impl MemoryTraceManager {
     pub fn flush(&mut self, _now: Instant) {
         for provider in &self.providers {
-            let provider_name = provider.name();
-            let ids = provider.get_children_ids();
-            for id in ids {
-                let sub_trace = provider.sub_trace(id);
-                let sub_trace_name = sub_trace.name();
-                MEM_TRACE_SUM_GAUGE
-                    .with_label_values(&[&format!("{}-{}", provider_name, sub_trace_name)])
-                    .set(sub_trace.sum() as i64)
+            let name = provider.name();
+            MEM_TRACE_SUM_GAUGE
+                .with_label_values(&[&name])
+                .set(provider.sum() as i64);
+            for id in provider.get_children_ids() {
+                Self::flush_trace(&provider.sub_trace(id), &name);
             }
+        }
+    }
 
-            MEM_TRACE_SUM_GAUGE
-                .with_label_values(&[&provider_name])
-                .set(provider.sum() as i64)
+    // Flushes the whole trace tree so that nested nodes like
+    // `raftstore-raft_router-alive` get their own gauge as well.
+    fn flush_trace(trace: &Arc<dyn MemoryTrace + Send + Sync>, prefix: &str) {
+        let name = format!("{}-{}", prefix, trace.name());
+        MEM_TRACE_SUM_GAUGE
+            .with_label_values(&[&name])
+            .set(trace.sum() as i64);
+        for id in trace.get_children_ids() {
+            Self::flush_trace(&trace.sub_trace(id), &name);
         }
     }
 
@@ -139,6 +139,7 @@ pub fn initial_logger(config: &TiKvConfig) {
         let drainer = logger::LogDispatcher::new(normal, rocksdb, raftdb, slow);
         let level = config.log_level;
         let slow_threshold = config.slow_log_threshold.as_millis();
+        logger::set_repeated_log_suppress_window(config.log_repeated_suppress_window.0);
         logger::init_log(drainer, level, true, true, vec![], slow_threshold).unwrap_or_else(|e| {
             fatal!("failed to initialize log: {}", e);
         });
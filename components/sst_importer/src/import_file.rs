@@ -8,7 +8,7 @@ use std::sync::Arc;
 
 use encryption::{DataKeyManager, EncrypterWriter};
 use engine_rocks::{get_env, RocksSstReader};
-use engine_traits::{EncryptionKeyManager, KvEngine, SSTMetaInfo, SstReader};
+use engine_traits::{EncryptionKeyManager, KvEngine, SSTMetaInfo, SstReader, CF_DEFAULT};
 use file_system::{get_io_rate_limiter, sync_dir, File, OpenOptions};
 use kvproto::import_sstpb::*;
 use tikv_util::time::Instant;
@@ -300,6 +300,10 @@ impl ImportDir {
             paths.entry(cf).or_insert_with(Vec::new).push(path);
         }
 
+        // Ingest the default CF before the write CF, so a read between the two
+        // ingestions never sees a write record whose value is still missing.
+        let mut paths: Vec<_> = paths.into_iter().collect();
+        paths.sort_by_key(|(cf, _)| (*cf != CF_DEFAULT) as u8);
         for (cf, cf_paths) in paths {
             let files: Vec<&str> = cf_paths.iter().map(|p| p.clone.to_str().unwrap()).collect();
             engine.ingest_external_file_cf(cf, &files)?;
@@ -373,7 +377,6 @@ pub fn path_to_sst_meta<P: AsRef<Path>>(path: P) -> Result<SstMeta> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use engine_traits::CF_DEFAULT;
 
     #[test]
     fn test_sst_meta_to_path() {
@@ -6,6 +6,7 @@ use std::time::Duration;
 use std::time::Instant;
 
 use engine_traits::{ColumnFamilyOptions, DBOptions, KvEngine};
+use file_system::{get_io_rate_limiter, IOPriority, IOType};
 use futures::executor::ThreadPool;
 use futures_util::compat::Future01CompatExt;
 use kvproto::import_sstpb::*;
@@ -20,6 +21,7 @@ struct ImportModeSwitcherInner {
     is_import: Arc<AtomicBool>,
     backup_db_options: ImportModeDBOptions,
     backup_cf_options: Vec<(String, ImportModeCFOptions)>,
+    backup_import_priority: Option<IOPriority>,
     timeout: Duration,
     next_check: Instant,
     metrics_fn: RocksDBMetricsFn,
@@ -35,6 +37,11 @@ impl ImportModeSwitcherInner {
         for (cf_name, cf_opts) in &self.backup_cf_options {
             cf_opts.set_options(db, cf_name, mf)?;
         }
+        if let (Some(limiter), Some(priority)) =
+            (get_io_rate_limiter(), self.backup_import_priority.take())
+        {
+            limiter.set_io_priority(IOType::Import, priority);
+        }
 
         info!("enter normal mode");
         self.is_import.store(false, Ordering::Release);
@@ -57,6 +64,12 @@ impl ImportModeSwitcherInner {
             self.backup_cf_options.push((cf_name.to_owned(), cf_opts));
             import_cf_options.set_options(db, cf_name, mf)?;
         }
+        // Import traffic should not be throttled behind foreground writes while
+        // the store is dedicated to bulk ingestion.
+        if let Some(limiter) = get_io_rate_limiter() {
+            self.backup_import_priority = Some(limiter.io_priority(IOType::Import));
+            limiter.set_io_priority(IOType::Import, IOPriority::High);
+        }
         info!("enter import mode");
         self.is_import.store(true, Ordering::Release);
         Ok(true)
@@ -79,6 +92,7 @@ impl ImportModeSwitcher {
             is_import: is_import.clone(),
             backup_db_options: ImportModeDBOptions::new(),
             backup_cf_options: Vec::new(),
+            backup_import_priority: None,
             timeout,
             next_check: Instant::now() + timeout,
             metrics_fn: mf,
@@ -182,6 +196,7 @@ struct ImportModeCFOptions {
     level0_slowdown_writes_trigger: u32,
     soft_pending_compaction_bytes_limit: u64,
     hard_pending_compaction_bytes_limit: u64,
+    disable_auto_compactions: bool,
 }
 
 impl ImportModeCFOptions {
@@ -191,6 +206,7 @@ impl ImportModeCFOptions {
             level0_slowdown_writes_trigger: self.level0_slowdown_writes_trigger.max(1 << 30),
             soft_pending_compaction_bytes_limit: 0,
             hard_pending_compaction_bytes_limit: 0,
+            disable_auto_compactions: true,
         }
     }
 
@@ -202,6 +218,7 @@ impl ImportModeCFOptions {
             level0_slowdown_writes_trigger: cf_opts.get_level_zero_slowdown_writes_trigger(),
             soft_pending_compaction_bytes_limit: cf_opts.get_soft_pending_compaction_bytes_limit(),
             hard_pending_compaction_bytes_limit: cf_opts.get_hard_pending_compaction_bytes_limit(),
+            disable_auto_compactions: cf_opts.get_disable_auto_compactions(),
         }
     }
 
@@ -223,6 +240,10 @@ impl ImportModeCFOptions {
                 "hard_pending_compaction_bytes_limit".to_owned(),
                 self.hard_pending_compaction_bytes_limit.to_string(),
             ),
+            (
+                "disable_auto_compactions".to_owned(),
+                self.disable_auto_compactions.to_string(),
+            ),
         ];
 
         let tmp_opts: Vec<_> = opts.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect();
@@ -278,6 +299,10 @@ mod tests {
                 cf_opts.get_hard_pending_compaction_bytes_limit(),
                 expected_cf_opts.hard_pending_compaction_bytes_limit
             );
+            assert_eq!(
+                cf_opts.get_disable_auto_compactions(),
+                expected_cf_opts.disable_auto_compactions
+            );
         }
     }
 
@@ -300,6 +325,7 @@ mod tests {
         );
         assert_eq!(import_cf_options.hard_pending_compaction_bytes_limit, 0);
         assert_eq!(import_cf_options.soft_pending_compaction_bytes_limit, 0);
+        assert!(import_cf_options.disable_auto_compactions);
         fn mf(_cf: &str, _name: &str, _v: f64) {}
 
         let cfg = Config::default();
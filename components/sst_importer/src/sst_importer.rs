@@ -229,6 +229,36 @@ impl SSTImporter {
             url
         };
 
+        // Check the length and the crc32 recorded in the meta before handing the
+        // file over to RocksDB, so a truncated or corrupted download is caught
+        // even when the SST block checksums happen to be consistent.
+        if meta.get_length() != 0 {
+            let file_length = file_system::get_file_size(&path.temp)?;
+            if meta.get_length() != file_length {
+                return Err(Error::FileCorrupted(
+                    path.temp,
+                    format!(
+                        "length {} does not match meta length {}",
+                        file_length,
+                        meta.get_length()
+                    ),
+                ));
+            }
+        }
+        if meta.get_crc32() != 0 {
+            let crc32 = file_system::calc_crc32(&path.temp)?;
+            if meta.get_crc32() != crc32 {
+                return Err(Error::FileCorrupted(
+                    path.temp,
+                    format!(
+                        "crc32 {} does not match meta crc32 {}",
+                        crc32,
+                        meta.get_crc32()
+                    ),
+                ));
+            }
+        }
+
         // now validate the SST file.
         let path_str = path.temp.to_str().unwrap();
         let env = get_env(self.key_manager.clone(), get_io_rate_limiter())?;
@@ -1338,6 +1368,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_download_sst_length_mismatch() {
+        let (_ext_sst_dir, backend, mut meta) = create_sample_external_sst_file().unwrap();
+        let importer_dir = tempfile::tempdir().unwrap();
+        let cfg = Config::default();
+        let importer = SSTImporter::new(&cfg, &importer_dir, None, false).unwrap();
+        let db = create_sst_test_engine().unwrap();
+        meta.set_length(meta.get_length() + 1);
+
+        let result = importer.download::<TestEngine>(
+            &meta,
+            &backend,
+            "sample.sst",
+            &RewriteRule::default(),
+            Limiter::new(INFINITY),
+            db,
+        );
+        match &result {
+            Err(Error::FileCorrupted(_, msg)) if msg.contains("length") => {}
+            _ => panic!("unexpected download result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_download_sst_crc32_mismatch() {
+        let (_ext_sst_dir, backend, mut meta) = create_sample_external_sst_file().unwrap();
+        let importer_dir = tempfile::tempdir().unwrap();
+        let cfg = Config::default();
+        let importer = SSTImporter::new(&cfg, &importer_dir, None, false).unwrap();
+        let db = create_sst_test_engine().unwrap();
+        meta.set_crc32(calc_data_crc32(b"corrupted"));
+
+        let result = importer.download::<TestEngine>(
+            &meta,
+            &backend,
+            "sample.sst",
+            &RewriteRule::default(),
+            Limiter::new(INFINITY),
+            db,
+        );
+        match &result {
+            Err(Error::FileCorrupted(_, msg)) if msg.contains("crc32") => {}
+            _ => panic!("unexpected download result: {:?}", result),
+        }
+    }
+
     #[test]
     fn test_download_sst_empty() {
         let (_ext_sst_dir, backend, mut meta) = create_sample_external_sst_file().unwrap();
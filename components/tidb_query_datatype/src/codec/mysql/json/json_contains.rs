@@ -0,0 +1,98 @@
+// Copyright 2021 TiKV Project Authors. Licensed under Apache-2.0.
+
+use std::cmp::Ordering;
+
+use super::super::Result;
+use super::{JsonRef, JsonType};
+
+impl<'a> JsonRef<'a> {
+    /// `json_contains` is the implementation for JSON_CONTAINS in mysql
+    /// <https://dev.mysql.com/doc/refman/5.7/en/json-search-functions.html#function_json-contains>
+    pub fn json_contains(&self, target: JsonRef<'a>) -> Result<bool> {
+        match self.get_type() {
+            JsonType::Object => {
+                if target.get_type() == JsonType::Object {
+                    for i in 0..target.get_elem_count() {
+                        let key = target.object_get_key(i);
+                        let val = target.object_get_val(i)?;
+                        match self.object_search_key(key) {
+                            Some(idx) => {
+                                if !self.object_get_val(idx)?.json_contains(val)? {
+                                    return Ok(false);
+                                }
+                            }
+                            None => return Ok(false),
+                        }
+                    }
+                    return Ok(true);
+                }
+                Ok(false)
+            }
+            JsonType::Array => {
+                if target.get_type() == JsonType::Array {
+                    for i in 0..target.get_elem_count() {
+                        if !self.json_contains(target.array_get_elem(i)?)? {
+                            return Ok(false);
+                        }
+                    }
+                    return Ok(true);
+                }
+                // A scalar is contained in an array if some element of the
+                // array contains it.
+                for i in 0..self.get_elem_count() {
+                    if self.array_get_elem(i)?.json_contains(target)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            _ => {
+                if target.get_type() == JsonType::Array {
+                    return Ok(false);
+                }
+                Ok(self.partial_cmp(&target) == Some(Ordering::Equal))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Json;
+
+    #[test]
+    fn test_json_contains() {
+        let mut test_cases = vec![
+            (r#"{}"#, r#"{}"#, true),
+            (r#"{"a":1}"#, r#"{}"#, true),
+            (r#"{"a":1}"#, r#"1"#, false),
+            (r#"{"a":{"a":1}}"#, r#"{"a":1}"#, false),
+            (r#"{"a":{"a":1}}"#, r#"{"a":{"a":1}}"#, true),
+            (r#"{"b":2, "c":3}"#, r#"{"c":3}"#, true),
+            (r#"1"#, r#"1"#, true),
+            (r#"[1]"#, r#"1"#, true),
+            (r#"[1,2]"#, r#"[1]"#, true),
+            (r#"[1,2]"#, r#"[1,3]"#, false),
+            (r#"[1,2]"#, r#"[[3]]"#, false),
+            (r#"[[1,2]]"#, r#"[[1]]"#, true),
+            (r#"[1,2,[1,3]]"#, r#"[1,3]"#, true),
+            (r#"[1,2,[1,[5,[3]]]]"#, r#"[1,3]"#, true),
+            (r#"[{"a":1}]"#, r#"{"a":1}"#, true),
+            (r#"[{"a":1,"b":2}]"#, r#"{"a":1}"#, true),
+            (r#"[{"a":{"a":1},"b":2}]"#, r#"{"a":1}"#, false),
+            (r#""hello""#, r#""hello""#, true),
+            (r#""hello""#, r#""world""#, false),
+            (r#"1"#, r#"[1]"#, false),
+        ];
+        for (i, (js, value, expected)) in test_cases.drain(..).enumerate() {
+            let j: Json = js.parse().unwrap();
+            let value: Json = value.parse().unwrap();
+            let got = j.as_ref().json_contains(value.as_ref()).unwrap();
+            assert_eq!(
+                got, expected,
+                "#{} expect {:?}, but got {:?}",
+                i, expected, got
+            );
+        }
+    }
+}
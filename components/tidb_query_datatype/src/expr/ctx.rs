@@ -70,6 +70,10 @@ pub struct EvalConfig {
     // warning is a executor stuff instead of a evaluation stuff.
     pub max_warning_cnt: usize,
     pub sql_mode: SqlMode,
+    // TODO: like max_warning_cnt, the memory quota is an ExecutionConfig. It
+    // bounds the estimated heap memory an executor may hold per request.
+    // 0 means no limit.
+    pub memory_quota: usize,
 }
 
 impl Default for EvalConfig {
@@ -105,6 +109,7 @@ impl EvalConfig {
             flag: Flag::empty(),
             max_warning_cnt: DEFAULT_MAX_WARNING_CNT,
             sql_mode: SqlMode::empty(),
+            memory_quota: 0,
         }
     }
 
@@ -124,6 +129,11 @@ impl EvalConfig {
         self
     }
 
+    pub fn set_memory_quota(&mut self, new_value: usize) -> &mut Self {
+        self.memory_quota = new_value;
+        self
+    }
+
     pub fn set_time_zone_by_name(&mut self, tz_name: &str) -> Result<&mut Self> {
         match Tz::from_tz_name(tz_name) {
             Some(tz) => {
@@ -1149,4 +1149,38 @@ mod tests {
             .collect();
         assert_eq!(&ordered_column, &[Some(1), Some(1), Some(2), Some(3)]);
     }
+
+    #[test]
+    fn test_memory_quota_exceeded() {
+        let group_by_exp = RpnExpressionBuilder::new_for_test()
+            .push_column_ref_for_test(0)
+            .build_for_test();
+        let aggr_definitions = vec![
+            ExprDefBuilder::aggr_func(ExprType::Count, FieldTypeTp::LongLong)
+                .push_child(ExprDefBuilder::constant_int(1))
+                .build(),
+        ];
+        let mut config = EvalConfig::default();
+        config.set_memory_quota(1);
+        let mut exec = BatchFastHashAggregationExecutor::new_impl(
+            Arc::new(config),
+            make_src_executor_1(),
+            group_by_exp,
+            aggr_definitions,
+            AllAggrDefinitionParser,
+        )
+        .unwrap();
+
+        // The quota is far below the cost of a single group, so the first
+        // batch carrying any row must fail the request.
+        for _ in 0..10 {
+            let r = exec.next_batch(1);
+            match r.is_drained {
+                Ok(false) => {}
+                Ok(true) => panic!("the memory quota should have been exceeded"),
+                Err(_) => return,
+            }
+        }
+        panic!("the memory quota should have been exceeded");
+    }
 }
@@ -343,10 +343,13 @@ impl<SS: 'static> BatchExecutorsRunner<SS> {
         deadline: Deadline,
         stream_row_limit: usize,
         is_streaming: bool,
+        memory_quota: usize,
     ) -> Result<Self> {
         let executors_len = req.get_executors().len();
         let collect_exec_summary = req.get_collect_execution_summaries();
-        let config = Arc::new(EvalConfig::from_request(&req)?);
+        let mut eval_cfg = EvalConfig::from_request(&req)?;
+        eval_cfg.set_memory_quota(memory_quota);
+        let config = Arc::new(eval_cfg);
 
         let out_most_executor = build_executors(
             req.take_executors().into(),
@@ -25,8 +25,14 @@
 //! Some aggregate function output multiple results, for example, `AVG(Int)` output two results:
 //! count and sum. In this case we say that the result of `AVG(Int)` has a *cardinality* of 2.
 //!
+//! Memory: aggregation holds its whole intermediate state in memory, so its
+//! estimated size is tracked against `EvalConfig::memory_quota` and an
+//! oversized GROUP BY fails the request instead of growing the process heap
+//! without bound. Spilling the state to temporary files instead of failing is
+//! left for a future rework.
 
 use std::convert::TryFrom;
+use std::mem;
 use std::sync::Arc;
 
 use tidb_query_datatype::{EvalType, FieldTypeAccessor};
@@ -113,12 +119,20 @@ pub struct Entities<Src: BatchExecutor> {
     pub all_result_column_types: Vec<EvalType>,
 }
 
+/// Estimated per-group heap overhead of the container entry and the group key,
+/// in addition to the aggregate function states.
+const EST_BYTES_PER_GROUP_OVERHEAD: usize = 64;
+
 /// A shared executor implementation for simple aggregation, hash aggregation and
 /// stream aggregation. Implementation differences are further given via `AggregationExecutorImpl`.
 pub struct AggregationExecutor<Src: BatchExecutor, I: AggregationExecutorImpl<Src>> {
     imp: I,
     is_ended: bool,
     entities: Entities<Src>,
+
+    /// Estimated heap bytes one group occupies, used to account the state
+    /// against `EvalConfig::memory_quota`.
+    est_bytes_per_group: usize,
 }
 
 impl<Src: BatchExecutor, I: AggregationExecutorImpl<Src>> AggregationExecutor<Src, I> {
@@ -179,10 +193,23 @@ impl<Src: BatchExecutor, I: AggregationExecutorImpl<Src>> AggregationExecutor<Sr
         };
         imp.prepare_entities(&mut entities);
 
+        // All groups carry one state per aggregate function, so sizing the
+        // states of one representative group is enough.
+        let est_bytes_per_group = entities
+            .each_aggr_fn
+            .iter()
+            .map(|aggr_fn| {
+                mem::size_of::<Box<dyn AggrFunctionState>>()
+                    + mem::size_of_val(&*aggr_fn.create_state())
+            })
+            .sum::<usize>()
+            + EST_BYTES_PER_GROUP_OVERHEAD;
+
         Ok(Self {
             imp,
             is_ended: false,
             entities,
+            est_bytes_per_group,
         })
     }
 
@@ -207,6 +234,7 @@ impl<Src: BatchExecutor, I: AggregationExecutorImpl<Src>> AggregationExecutor<Sr
                 src_result.physical_columns,
                 &src_result.logical_rows,
             )?;
+            self.check_memory_quota()?;
         }
 
         // aggregate result is always available when source is drained
@@ -218,6 +246,23 @@ impl<Src: BatchExecutor, I: AggregationExecutorImpl<Src>> AggregationExecutor<Sr
         Ok((result, src_is_drained))
     }
 
+    /// Fails the request when the estimated memory held by the aggregation
+    /// state exceeds the per-request quota. A quota of 0 disables the check.
+    fn check_memory_quota(&self) -> Result<()> {
+        let quota = self.entities.context.cfg.memory_quota;
+        if quota > 0 {
+            let usage = self.imp.groups_len() * self.est_bytes_per_group;
+            if usage > quota {
+                return Err(other_err!(
+                    "aggregation exceeds the memory quota, estimated usage: {}, quota: {}",
+                    usage,
+                    quota
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Generates aggregation results of available groups.
     fn aggregate_partial_results(&mut self, src_is_drained: bool) -> Result<LazyBatchColumnVec> {
         let groups_len = self.imp.groups_len();
@@ -217,6 +217,49 @@ fn json_unquote(arg: BytesRef) -> Result<Option<Bytes>> {
 }
 
 // Args should be like `(Option<JsonRef> , &[Option<BytesRef>])`.
+fn json_contains_validator(expr: &tipb::Expr) -> Result<()> {
+    let children = expr.get_children();
+    assert!(children.len() == 2 || children.len() == 3);
+    super::function::validate_expr_return_type(&children[0], EvalType::Json)?;
+    super::function::validate_expr_return_type(&children[1], EvalType::Json)?;
+    if children.len() == 3 {
+        super::function::validate_expr_return_type(&children[2], EvalType::Bytes)?;
+    }
+    Ok(())
+}
+
+#[rpn_fn(nullable, raw_varg, min_args = 2, max_args = 3, extra_validator = json_contains_validator)]
+#[inline]
+fn json_contains(args: &[ScalarValueRef]) -> Result<Option<Int>> {
+    assert!(args.len() == 2 || args.len() == 3);
+    let j: Option<JsonRef> = args[0].as_json();
+    let j = match j {
+        None => return Ok(None),
+        Some(j) => j.to_owned(),
+    };
+    let target: Option<JsonRef> = args[1].as_json();
+    let target = match target {
+        None => return Ok(None),
+        Some(target) => target.to_owned(),
+    };
+    let mut j = j.as_ref();
+    let extracted;
+    if args.len() == 3 {
+        let path = try_opt!(parse_json_path(args[2].as_bytes()));
+        if path.contains_any_asterisk() {
+            return Err(other_err!("Invalid JSON path expression"));
+        }
+        match j.extract(&[path])? {
+            Some(json) => {
+                extracted = json;
+                j = extracted.as_ref();
+            }
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(j.json_contains(target.as_ref())? as Int))
+}
+
 fn json_with_paths_validator(expr: &tipb::Expr) -> Result<()> {
     assert!(expr.get_children().len() >= 2);
     // args should be like `Option<JsonRef> , &[Option<BytesRef>]`.
@@ -737,6 +780,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_json_contains() {
+        let cases: Vec<(Vec<ScalarValue>, Option<i64>)> = vec![
+            (
+                vec![
+                    None::<Json>.into(),
+                    Some(Json::from_str("1").unwrap()).into(),
+                ],
+                None,
+            ),
+            (
+                vec![
+                    Some(Json::from_str(r#"{"a":1}"#).unwrap()).into(),
+                    Some(Json::from_str(r#"{}"#).unwrap()).into(),
+                ],
+                Some(1),
+            ),
+            (
+                vec![
+                    Some(Json::from_str(r#"[1,2,[1,3]]"#).unwrap()).into(),
+                    Some(Json::from_str(r#"[1,3]"#).unwrap()).into(),
+                ],
+                Some(1),
+            ),
+            (
+                vec![
+                    Some(Json::from_str(r#"{"a":{"b":[1,2]}}"#).unwrap()).into(),
+                    Some(Json::from_str("1").unwrap()).into(),
+                    Some(b"$.a.b".to_vec()).into(),
+                ],
+                Some(1),
+            ),
+            (
+                vec![
+                    Some(Json::from_str(r#"{"a":{"b":[1,2]}}"#).unwrap()).into(),
+                    Some(Json::from_str("1").unwrap()).into(),
+                    Some(b"$.a.c".to_vec()).into(),
+                ],
+                None,
+            ),
+            (
+                vec![
+                    Some(Json::from_str(r#"{"a":1}"#).unwrap()).into(),
+                    Some(Json::from_str("2").unwrap()).into(),
+                ],
+                Some(0),
+            ),
+        ];
+
+        for (vargs, expected) in cases {
+            let output = RpnFnScalarEvaluator::new()
+                .push_params(vargs.clone())
+                .evaluate(ScalarFuncSig::JsonContainsSig)
+                .unwrap();
+            assert_eq!(output, expected, "{:?}", vargs);
+        }
+    }
+
     #[test]
     fn test_json_length() {
         let cases: Vec<(Vec<ScalarValue>, Option<i64>)> = vec![
@@ -10,6 +10,11 @@ use tidb_query_common::Result;
 use tidb_query_datatype::codec::collation::*;
 use tidb_query_datatype::codec::data_type::*;
 
+// When the pattern is a literal, `metadata_mapper` compiles it once at plan
+// build time and every row only runs the match. A non-literal pattern (rare;
+// e.g. a pattern read from another column) recompiles per row — caching those
+// would need a per-expression LRU keyed by the pattern bytes.
+
 #[rpn_fn]
 #[inline]
 pub fn like<C: Collator>(target: BytesRef, pattern: BytesRef, escape: &i64) -> Result<Option<i64>> {
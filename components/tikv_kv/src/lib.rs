@@ -455,6 +455,14 @@ pub unsafe fn destroy_tls_engine<E: Engine>() {
 }
 
 /// Get a snapshot of `engine`.
+///
+/// This is the futures-based form of [`Engine::async_snapshot`] and the one
+/// the read path uses: `Storage` and the coprocessor await it on the unified
+/// read pool, so no worker thread blocks while the raftstore processes the
+/// read index. It stays a free function instead of a trait method because a
+/// future-returning trait method would have to box its future on every call,
+/// and because the engine reference must not be held across the yield point
+/// (see below).
 pub fn snapshot<E: Engine>(
     engine: &E,
     ctx: SnapContext<'_>,
@@ -3,13 +3,16 @@
 mod file_log;
 mod formatter;
 
+use std::collections::hash_map::Entry as HashMapEntry;
+use std::collections::HashMap;
 use std::env;
 use std::fmt;
 use std::io::{self, BufWriter};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Mutex;
 use std::thread;
+use std::time::{Duration, Instant};
 
 use log::{self, SetLoggerError};
 use slog::{self, slog_o, Drain, FnValue, Key, OwnedKVList, PushFnValue, Record, KV};
@@ -86,6 +89,7 @@ where
             threshold: slow_threshold,
             inner: drain,
         };
+        let drain = RepeatedLogFilter::new(drain);
         let filtered = drain.filter(filter).fuse();
 
         (slog::Logger::root(filtered, slog_o!()), Some(guard))
@@ -95,6 +99,7 @@ where
             threshold: slow_threshold,
             inner: drain,
         };
+        let drain = RepeatedLogFilter::new(drain);
         let filtered = drain.filter(filter).fuse();
         (slog::Logger::root(filtered, slog_o!()), None)
     };
@@ -384,6 +389,121 @@ where
     }
 }
 
+// How long identical warning (or above) messages are suppressed for after one
+// of them is logged, unless overridden by `set_repeated_log_suppress_window`.
+const DEFAULT_REPEATED_LOG_SUPPRESS_WINDOW: Duration = Duration::from_secs(1);
+// Bound on the number of distinct suppressed messages tracked at a time.
+const REPEATED_LOG_STATE_CAPACITY: usize = 256;
+
+static REPEATED_LOG_SUPPRESS_WINDOW_MS: AtomicU64 =
+    AtomicU64::new(DEFAULT_REPEATED_LOG_SUPPRESS_WINDOW.as_millis() as u64);
+
+/// Sets how long repeated warning (and above) messages are suppressed for.
+/// A zero duration disables the suppression entirely.
+pub fn set_repeated_log_suppress_window(window: Duration) {
+    REPEATED_LOG_SUPPRESS_WINDOW_MS.store(window.as_millis() as u64, Ordering::Relaxed);
+}
+
+/// Serializes key-value pairs into the deduplication key, so that two records
+/// sharing a static message but differing in their variable parts (region id,
+/// store id, error cause) are not treated as repetitions of each other.
+struct RepeatedLogKeySerializer<'a>(&'a mut String);
+
+impl slog::Serializer for RepeatedLogKeySerializer<'_> {
+    fn emit_arguments(&mut self, key: Key, val: &fmt::Arguments<'_>) -> slog::Result {
+        use std::fmt::Write;
+        let _ = write!(self.0, " {}={}", key, val);
+        Ok(())
+    }
+}
+
+struct RepeatedLogState {
+    last: Instant,
+    suppressed: u64,
+}
+
+/// Suppresses identical warning (and above) records arriving within the
+/// suppression window of the last logged one, so that a hot error path can
+/// not flood the log.
+///
+/// A record is a repetition only if both its message and all its key-value
+/// pairs match. When a suppressed record is logged again after the window
+/// expires, a summary carrying the number of dropped duplicates is emitted
+/// alongside it.
+struct RepeatedLogFilter<D> {
+    inner: D,
+    state: Mutex<HashMap<String, RepeatedLogState>>,
+}
+
+impl<D> RepeatedLogFilter<D> {
+    fn new(inner: D) -> Self {
+        Self {
+            inner,
+            state: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<D> Drain for RepeatedLogFilter<D>
+where
+    D: Drain<Ok = (), Err = slog::Never>,
+{
+    type Ok = ();
+    type Err = slog::Never;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        let window_ms = REPEATED_LOG_SUPPRESS_WINDOW_MS.load(Ordering::Relaxed);
+        if window_ms == 0 || !record.level().is_at_least(Level::Warning) {
+            return self.inner.log(record, values);
+        }
+        let window = Duration::from_millis(window_ms);
+        let mut key = format!("{}", record.msg());
+        {
+            let mut s = RepeatedLogKeySerializer(&mut key);
+            let _ = record.kv().serialize(record, &mut s);
+            let _ = values.serialize(record, &mut s);
+        }
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        if state.len() >= REPEATED_LOG_STATE_CAPACITY {
+            // Evicted entries forget their suppressed counts; losing a summary
+            // is preferable to unbounded growth.
+            state.retain(|_, s| now.saturating_duration_since(s.last) < window);
+        }
+        let suppressed = match state.entry(key) {
+            HashMapEntry::Occupied(mut e) => {
+                let s = e.get_mut();
+                if now.saturating_duration_since(s.last) < window {
+                    s.suppressed += 1;
+                    return Ok(());
+                }
+                s.last = now;
+                std::mem::take(&mut s.suppressed)
+            }
+            HashMapEntry::Vacant(e) => {
+                e.insert(RepeatedLogState {
+                    last: now,
+                    suppressed: 0,
+                });
+                0
+            }
+        };
+        drop(state);
+        self.inner.log(record, values)?;
+        if suppressed > 0 {
+            self.inner.log(
+                &Record::new(
+                    &slog::record_static!(record.level(), record.tag()),
+                    &format_args!("suppressed repeated log records"),
+                    slog::b!("msg" => %record.msg(), "count" => suppressed),
+                ),
+                values,
+            )?;
+        }
+        Ok(())
+    }
+}
+
 // Filters logs with operation cost lower than threshold. Otherwise output logs to inner drainer
 struct SlowLogFilter<D> {
     threshold: u64,
@@ -917,6 +1037,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_repeated_log_filter() {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Arc;
+
+        struct CountingDrain(Arc<AtomicUsize>);
+        impl Drain for CountingDrain {
+            type Ok = ();
+            type Err = slog::Never;
+            fn log(&self, _: &Record<'_>, _: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }
+        }
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let drain = RepeatedLogFilter::new(CountingDrain(count.clone())).fuse();
+        let logger = slog::Logger::root_typed(drain, slog_o!());
+        // The second identical warning within the window is suppressed.
+        slog_warn!(logger, "dup");
+        slog_warn!(logger, "dup");
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        // A different message passes.
+        slog_warn!(logger, "other");
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+        // Records differing only in key-value pairs are not repetitions.
+        slog_warn!(logger, "kv"; "region_id" => 1);
+        slog_warn!(logger, "kv"; "region_id" => 2);
+        assert_eq!(count.load(Ordering::SeqCst), 4);
+        // Levels below warning are never throttled.
+        slog_info!(logger, "dup");
+        slog_info!(logger, "dup");
+        assert_eq!(count.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    fn test_repeated_log_filter_summary() {
+        use std::sync::Arc;
+
+        struct MsgCollector(Arc<Mutex<Vec<String>>>);
+        impl Drain for MsgCollector {
+            type Ok = ();
+            type Err = slog::Never;
+            fn log(&self, record: &Record<'_>, _: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+                self.0.lock().unwrap().push(format!("{}", record.msg()));
+                Ok(())
+            }
+        }
+
+        let msgs = Arc::new(Mutex::new(Vec::new()));
+        let filter = RepeatedLogFilter::new(MsgCollector(msgs.clone()));
+        let logger = slog::Logger::root_typed((&filter).fuse(), slog_o!());
+        slog_warn!(logger, "dup");
+        slog_warn!(logger, "dup");
+        slog_warn!(logger, "dup");
+        // Force the window to expire and log the message again: the record
+        // passes and a summary of the two dropped duplicates follows it.
+        filter
+            .state
+            .lock()
+            .unwrap()
+            .get_mut("dup")
+            .unwrap()
+            .last -= 2 * DEFAULT_REPEATED_LOG_SUPPRESS_WINDOW;
+        slog_warn!(logger, "dup");
+        let msgs = msgs.lock().unwrap();
+        assert_eq!(
+            *msgs,
+            vec![
+                "dup".to_owned(),
+                "dup".to_owned(),
+                "suppressed repeated log records".to_owned()
+            ]
+        );
+    }
+
     #[test]
     fn test_slow_log_dispatcher() {
         let normal = TikvFormat::new(PlainSyncDecorator::new(NormalWriter));
@@ -397,7 +397,9 @@ impl ThreadInfoStatistics {
 
             if let Ok(stat) = pid::stat_task(self.pid, tid) {
                 let name = get_name(&stat.command);
-                self.tid_names.entry(tid).or_insert(name);
+                // Thread ids get recycled by the OS, so always refresh the
+                // name to avoid attributing a reused tid to the wrong pool.
+                self.tid_names.insert(tid, name);
 
                 // To get a percentage result,
                 // we pre-multiply `cpu_time` by 100 here rather than inside the `update_metric`.
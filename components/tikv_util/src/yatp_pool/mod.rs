@@ -156,6 +156,7 @@ pub struct YatpPoolBuilder<T: PoolTicker> {
     max_thread_count: usize,
     stack_size: usize,
     max_tasks: usize,
+    level0_proportion_target: Option<f64>,
 }
 
 impl<T: PoolTicker> YatpPoolBuilder<T> {
@@ -170,6 +171,7 @@ impl<T: PoolTicker> YatpPoolBuilder<T> {
             max_thread_count: 1,
             stack_size: 0,
             max_tasks: std::usize::MAX,
+            level0_proportion_target: None,
         }
     }
 
@@ -201,6 +203,14 @@ impl<T: PoolTicker> YatpPoolBuilder<T> {
         self
     }
 
+    /// Sets the proportion of CPU time that level 0 tasks should get in a
+    /// multi level pool. The rest is left to tasks that have accumulated
+    /// enough CPU time to be degraded to lower levels.
+    pub fn level0_proportion_target(&mut self, target: f64) -> &mut Self {
+        self.level0_proportion_target = Some(target);
+        self
+    }
+
     pub fn before_stop<F>(&mut self, f: F) -> &mut Self
     where
         F: Fn() + Send + Sync + 'static,
@@ -242,8 +252,11 @@ impl<T: PoolTicker> YatpPoolBuilder<T> {
     pub fn build_multi_level_pool(&mut self) -> ThreadPool<TaskCell> {
         let (builder, read_pool_runner) = self.create_builder();
         let name = self.name_prefix.as_deref().unwrap_or("yatp_pool");
-        let multilevel_builder =
-            multilevel::Builder::new(multilevel::Config::default().name(Some(name)));
+        let mut config = multilevel::Config::default().name(Some(name));
+        if let Some(target) = self.level0_proportion_target {
+            config = config.level0_proportion_target(target);
+        }
+        let multilevel_builder = multilevel::Builder::new(config);
         let runner_builder =
             multilevel_builder.runner_builder(CloneRunnerBuilder(read_pool_runner));
         builder
@@ -94,6 +94,12 @@ fn memory_limit_for_cf(is_raft_db: bool, cf: &str, total_mem: u64) -> ReadableSi
     ReadableSize::mb(size as u64 / MIB)
 }
 
+// Titan's blob GC needs no coordination with the MVCC safe point: it only
+// discards a blob value after every LSM key referencing it is gone, and live
+// references are rewritten into new blob files. MVCC versions older than the
+// safe point are removed from the LSM by TiKV's own GC first, so a backup or
+// read at an old ts can never follow a dangling blob reference. Blob GC
+// progress is visible through the "titandb_*" engine metrics.
 #[derive(Clone, Serialize, Deserialize, PartialEq, Debug, OnlineConfig)]
 #[serde(default)]
 #[serde(rename_all = "kebab-case")]
@@ -1666,6 +1672,10 @@ pub struct UnifiedReadPoolConfig {
     pub max_thread_count: usize,
     pub stack_size: ReadableSize,
     pub max_tasks_per_worker: usize,
+    /// The proportion of CPU time reserved for tasks that have not yet
+    /// accumulated much CPU time. The rest is the guaranteed minimum share of
+    /// long running tasks, which protects them from starvation.
+    pub level0_proportion_target: f64,
     // FIXME: Add more configs when they are effective in yatp
 }
 
@@ -1693,6 +1703,13 @@ impl UnifiedReadPoolConfig {
                 .to_string()
                 .into());
         }
+        if self.level0_proportion_target <= 0.0 || self.level0_proportion_target >= 1.0 {
+            return Err(
+                "readpool.unified.level0-proportion-target should be in (0, 1)"
+                    .to_string()
+                    .into(),
+            );
+        }
         Ok(())
     }
 }
@@ -1710,6 +1727,8 @@ impl Default for UnifiedReadPoolConfig {
             max_thread_count: concurrency,
             stack_size: ReadableSize::mb(DEFAULT_READPOOL_STACK_SIZE_MB),
             max_tasks_per_worker: DEFAULT_READPOOL_MAX_TASKS_PER_WORKER,
+            // Same as the default of yatp.
+            level0_proportion_target: 0.8,
         }
     }
 }
@@ -1725,6 +1744,7 @@ mod unified_read_pool_tests {
             max_thread_count: 2,
             stack_size: ReadableSize::mb(2),
             max_tasks_per_worker: 2000,
+            level0_proportion_target: 0.8,
         };
         assert!(cfg.validate().is_ok());
 
@@ -1752,6 +1772,12 @@ mod unified_read_pool_tests {
             ..cfg
         };
         assert!(invalid_cfg.validate().is_err());
+
+        let invalid_cfg = UnifiedReadPoolConfig {
+            level0_proportion_target: 1.2,
+            ..cfg
+        };
+        assert!(invalid_cfg.validate().is_err());
     }
 }
 
@@ -2042,6 +2068,7 @@ mod readpool_tests {
             max_thread_count: 0,
             stack_size: ReadableSize::mb(0),
             max_tasks_per_worker: 0,
+            level0_proportion_target: 0.0,
         };
         assert!(unified.validate().is_err());
         let storage = StorageReadPoolConfig {
@@ -2185,6 +2212,11 @@ pub struct BackupConfig {
     pub num_threads: usize,
     pub batch_size: usize,
     pub sst_max_size: ReadableSize,
+    // Path of a file that stores a hex encoded AES-256 key. When set, backup
+    // SST files are encrypted with AES-256-CTR before they are written to the
+    // external storage.
+    #[online_config(skip)]
+    pub sst_encryption_key_file: String,
 }
 
 impl BackupConfig {
@@ -2195,10 +2227,30 @@ impl BackupConfig {
         if self.batch_size == 0 {
             return Err("backup.batch_size cannot be 0".into());
         }
+        if !self.sst_encryption_key_file.is_empty() {
+            // Fail early on a malformed key instead of on the first backup.
+            load_sst_encryption_key(&self.sst_encryption_key_file)?;
+        }
         Ok(())
     }
 }
 
+/// Loads and decodes the backup SST encryption key from the given file.
+pub fn load_sst_encryption_key(path: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("backup.sst-encryption-key-file cannot be read: {}", e))?;
+    let key = hex::decode(content.trim())
+        .map_err(|e| format!("backup.sst-encryption-key-file is not hex encoded: {}", e))?;
+    if key.len() != 32 {
+        return Err(format!(
+            "backup.sst-encryption-key-file must store a 256-bit key, got {} bits",
+            key.len() * 8
+        )
+        .into());
+    }
+    Ok(key)
+}
+
 impl Default for BackupConfig {
     fn default() -> Self {
         let default_coprocessor = CopConfig::default();
@@ -2208,6 +2260,7 @@ impl Default for BackupConfig {
             num_threads: (cpu_num * 0.75).clamp(1.0, 32.0) as usize,
             batch_size: 8,
             sst_max_size: default_coprocessor.region_max_size,
+            sst_encryption_key_file: String::new(),
         }
     }
 }
@@ -2336,6 +2389,11 @@ pub struct TiKvConfig {
     #[online_config(skip)]
     pub log_rotation_size: ReadableSize,
 
+    /// How long repeated warning (and above) log records are suppressed for.
+    /// 0 disables the suppression.
+    #[online_config(skip)]
+    pub log_repeated_suppress_window: ReadableDuration,
+
     #[online_config(hidden)]
     pub panic_when_unexpected_key_or_data: bool,
 
@@ -2426,6 +2484,7 @@ impl Default for TiKvConfig {
             slow_log_threshold: ReadableDuration::secs(1),
             log_rotation_timespan: ReadableDuration::hours(24),
             log_rotation_size: ReadableSize::mb(300),
+            log_repeated_suppress_window: ReadableDuration::secs(1),
             panic_when_unexpected_key_or_data: false,
             enable_io_snoop: true,
             abort_on_panic: false,
@@ -2833,8 +2892,10 @@ impl TiKvConfig {
             Ok(cfg)
         })()
         .unwrap_or_else(|e| {
+            // This loads user-specified config files as well as the auto
+            // generated `last_tikv.toml`, so don't claim it's the latter.
             panic!(
-                "invalid auto generated configuration file {}, err {}",
+                "invalid configuration file {}, err {}",
                 path.display(),
                 e
             );
@@ -3194,7 +3255,9 @@ impl ConfigController {
                 }
             }
         }
-        debug!("all config change had been dispatched"; "change" => ?to_update);
+        // Online config changes are rare and operators need a durable record
+        // of them, so log at info level.
+        info!("all config change had been dispatched"; "change" => ?to_update);
         inner.current.update(to_update);
         // Write change to the config file
         let content = {
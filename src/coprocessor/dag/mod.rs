@@ -23,6 +23,7 @@ pub struct DagHandlerBuilder<S: Store + 'static> {
     batch_row_limit: usize,
     is_streaming: bool,
     is_cache_enabled: bool,
+    memory_quota: usize,
 }
 
 impl<S: Store + 'static> DagHandlerBuilder<S> {
@@ -44,6 +45,7 @@ impl<S: Store + 'static> DagHandlerBuilder<S> {
             batch_row_limit,
             is_streaming,
             is_cache_enabled,
+            memory_quota: 0,
         }
     }
 
@@ -52,6 +54,11 @@ impl<S: Store + 'static> DagHandlerBuilder<S> {
         self
     }
 
+    pub fn memory_quota(mut self, memory_quota: usize) -> Self {
+        self.memory_quota = memory_quota;
+        self
+    }
+
     pub fn build(self) -> Result<Box<dyn RequestHandler>> {
         COPR_DAG_REQ_COUNT.with_label_values(&["batch"]).inc();
         Ok(BatchDAGHandler::new(
@@ -63,6 +70,7 @@ impl<S: Store + 'static> DagHandlerBuilder<S> {
             self.is_cache_enabled,
             self.batch_row_limit,
             self.is_streaming,
+            self.memory_quota,
         )?
         .into_boxed())
     }
@@ -83,6 +91,7 @@ impl BatchDAGHandler {
         is_cache_enabled: bool,
         streaming_batch_limit: usize,
         is_streaming: bool,
+        memory_quota: usize,
     ) -> Result<Self> {
         Ok(Self {
             runner: tidb_query_executors::runner::BatchExecutorsRunner::from_request(
@@ -92,6 +101,7 @@ impl BatchDAGHandler {
                 deadline,
                 streaming_batch_limit,
                 is_streaming,
+                memory_quota,
             )?,
             data_version,
         })
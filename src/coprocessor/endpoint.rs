@@ -64,6 +64,10 @@ pub struct Endpoint<E: Engine> {
     stream_batch_row_limit: usize,
     stream_channel_size: usize,
 
+    /// Max estimated memory a request may hold in executor state. 0 means no
+    /// limit.
+    memory_quota: usize,
+
     /// The soft time limit of handling Coprocessor requests.
     max_handle_duration: Duration,
 
@@ -99,6 +103,7 @@ impl<E: Engine> Endpoint<E> {
             batch_row_limit: cfg.end_point_batch_row_limit,
             stream_batch_row_limit: cfg.end_point_stream_batch_row_limit,
             stream_channel_size: cfg.end_point_stream_channel_size,
+            memory_quota: cfg.end_point_memory_quota.0 as usize,
             max_handle_duration: cfg.end_point_request_max_handle_duration.0,
             slow_log_threshold: cfg.end_point_slow_log_threshold.0,
             _phantom: Default::default(),
@@ -247,6 +252,7 @@ impl<E: Engine> Endpoint<E> {
                 self.check_memory_locks(&req_ctx)?;
 
                 let batch_row_limit = self.get_batch_row_limit(is_streaming);
+                let memory_quota = self.memory_quota;
                 builder = Box::new(move |snap, req_ctx| {
                     let data_version = snap.get_data_version();
                     let store = SnapshotStore::new(
@@ -267,6 +273,7 @@ impl<E: Engine> Endpoint<E> {
                         req.get_is_cache_enabled(),
                     )
                     .data_version(data_version)
+                    .memory_quota(memory_quota)
                     .build()
                 });
             }
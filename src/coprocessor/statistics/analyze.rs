@@ -400,6 +400,12 @@ impl<S: Snapshot> RowSampleBuilder<S> {
     }
 }
 
+/// The "row collector v2" used by full sampling ANALYZE: one scan collects a
+/// weighted reservoir sample of whole rows plus per-column null counts,
+/// FM-sketch distinct estimates and total sizes. A Bernoulli variant would
+/// keep each row with a fixed probability instead of filling a bounded
+/// reservoir, but `AnalyzeColumnsReq` only expresses a sample count
+/// (`sample_size`), not a rate, so TiDB has no way to ask for it.
 #[derive(Clone)]
 struct RowSampleCollector {
     samples: BinaryHeap<Reverse<(i64, Vec<Vec<u8>>)>>,
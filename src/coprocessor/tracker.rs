@@ -279,6 +279,11 @@ impl Tracker {
                     => self.total_perf_stats.0.internal_key_skipped_count,
                 "perf_stats.internal_delete_skipped_count"
                     => self.total_perf_stats.0.internal_delete_skipped_count,
+                // The fields below have no counterpart in ScanDetailV2, the
+                // slow log is the only place they are surfaced.
+                "perf_stats.block_read_time" => self.total_perf_stats.0.block_read_time,
+                "perf_stats.seek_child_seek_count"
+                    => self.total_perf_stats.0.seek_child_seek_count,
             );
         }
 
@@ -327,6 +332,11 @@ impl Tracker {
 
         tls_collect_scan_details(self.req_ctx.tag, &total_storage_stats);
         tls_collect_read_flow(self.req_ctx.context.get_region_id(), &total_storage_stats);
+        crate::storage::metrics::tls_collect_follower_read_flow(
+            self.req_ctx.context.get_replica_read(),
+            self.req_ctx.context.get_stale_read(),
+            &total_storage_stats,
+        );
         tls_collect_perf_stats(self.req_ctx.tag, &self.total_perf_stats);
 
         let peer = self.req_ctx.context.get_peer();
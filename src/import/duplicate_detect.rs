@@ -71,7 +71,12 @@ impl<S: Snapshot> DuplicateDetector<S> {
         while self.iter.valid().map_err(from_kv_error)? {
             let (current_key, commit_ts) = Key::split_on_ts_for(self.iter.key())?;
             if commit_ts > self.min_commit_ts {
-                return Ok(Some((current_key.to_vec(), commit_ts)));
+                let write = WriteRef::parse(self.iter.value()).map_err(from_txn_types_error)?;
+                // Lock and Rollback records are not data versions, so they
+                // must not decide whether the key is a duplicate.
+                if !matches!(write.write_type, WriteType::Lock | WriteType::Rollback) {
+                    return Ok(Some((current_key.to_vec(), commit_ts)));
+                }
             }
             self.iter.next().map_err(from_kv_error)?;
         }
@@ -487,4 +492,29 @@ mod tests {
         let detector = DuplicateDetector::new(snapshot, b"0".to_vec(), None, 13, false).unwrap();
         check_duplicate_data(detector, expected_kvs);
     }
+
+    // A rollback record with a commit timestamp larger than min_commit_ts is
+    // not a data version, so it must neither be reported as a duplicate nor
+    // fail the detection.
+    #[test]
+    fn test_duplicate_detect_rollback_above_min_commit_ts() {
+        let storage = TestStorageBuilder::new(DummyLockManager {}, false)
+            .build()
+            .unwrap();
+        let data = vec![
+            (b"100".to_vec(), b"100".to_vec()),
+            (b"101".to_vec(), b"101".to_vec()),
+        ];
+        write_data(&storage, data.clone(), 10);
+        prewrite_data(&storage, b"100".to_vec(), data[..1].to_vec(), 15);
+        rollback_data(&storage, vec![b"100".to_vec()], 15);
+        write_data(&storage, data[1..].to_vec(), 16);
+        let expected_kvs = vec![
+            (b"101".to_vec(), b"101".to_vec(), 16),
+            (b"101".to_vec(), b"101".to_vec(), 10),
+        ];
+        let snapshot = storage.get_snapshot();
+        let detector = DuplicateDetector::new(snapshot, b"0".to_vec(), None, 13, false).unwrap();
+        check_duplicate_data(detector, expected_kvs);
+    }
 }
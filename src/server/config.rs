@@ -130,6 +130,10 @@ pub struct Config {
     pub end_point_request_max_handle_duration: ReadableDuration,
     #[online_config(skip)]
     pub end_point_max_concurrency: usize,
+    /// Max estimated memory a coprocessor request may hold in executor state
+    /// (e.g. aggregation groups). 0 means no limit.
+    #[online_config(skip)]
+    pub end_point_memory_quota: ReadableSize,
     pub snap_max_write_bytes_per_sec: ReadableSize,
     pub snap_max_total_size: ReadableSize,
     #[online_config(skip)]
@@ -231,6 +235,7 @@ impl Default for Config {
                 DEFAULT_ENDPOINT_REQUEST_MAX_HANDLE_SECS,
             ),
             end_point_max_concurrency: cmp::max(cpu_num as usize, MIN_ENDPOINT_MAX_CONCURRENCY),
+            end_point_memory_quota: ReadableSize(0),
             snap_max_write_bytes_per_sec: ReadableSize(DEFAULT_SNAP_MAX_BYTES_PER_SEC),
             snap_max_total_size: ReadableSize(0),
             stats_concurrency: 1,
@@ -317,6 +322,13 @@ impl Config {
             }
         }
 
+        if self.grpc_memory_pool_quota.0 < ReadableSize::mb(512).0 {
+            return Err(box_err!(
+                "server.grpc-memory-pool-quota is too small, \
+                 it should be no less than 512MiB"
+            ));
+        }
+
         if self.end_point_recursion_limit < 100 {
             return Err(box_err!("server.end-point-recursion-limit is too small"));
         }
@@ -447,6 +459,10 @@ mod tests {
         invalid_cfg.end_point_recursion_limit = 0;
         assert!(invalid_cfg.validate().is_err());
 
+        let mut invalid_cfg = cfg.clone();
+        invalid_cfg.grpc_memory_pool_quota = ReadableSize::mb(1);
+        assert!(invalid_cfg.validate().is_err());
+
         let mut invalid_cfg = cfg.clone();
         invalid_cfg.end_point_request_max_handle_duration = ReadableDuration::secs(0);
         assert!(invalid_cfg.validate().is_err());
@@ -555,6 +555,7 @@ impl<ER: RaftEngine> Debugger<ER> {
         &self,
         store_ids: Vec<u64>,
         region_ids: Option<Vec<u64>>,
+        dry_run: bool,
     ) -> Result<()> {
         let store_id = self.get_store_id()?;
         if store_ids.iter().any(|&s| s == store_id) {
@@ -582,6 +583,15 @@ impl<ER: RaftEngine> Debugger<ER> {
 
                 let region_id = region_state.get_region().get_id();
                 let old_peers = region_state.mut_region().take_peers();
+                if dry_run {
+                    info!(
+                        "would change peers";
+                        "region_id" => region_id,
+                        "old_peers" => ?old_peers,
+                        "new_peers" => ?new_peers,
+                    );
+                    return Ok(());
+                }
                 info!(
                     "peers changed";
                     "region_id" => region_id,
@@ -616,6 +626,9 @@ impl<ER: RaftEngine> Debugger<ER> {
             }
         }
 
+        if dry_run {
+            return Ok(());
+        }
         let mut write_opts = WriteOptions::new();
         write_opts.set_sync(true);
         box_try!(wb.write_opt(&write_opts));
@@ -1610,9 +1623,15 @@ mod tests {
         // region 2 with peers at stores 21, 22 and 23.
         init_region_state(engine.as_inner(), 2, &[21, 22, 23]);
 
+        // A dry run never touches the region state.
+        debugger
+            .remove_failed_stores(vec![13, 14, 21, 23], Some(vec![1]), true)
+            .unwrap();
+        assert_eq!(get_region_stores(engine.as_inner(), 1), &[11, 12, 13, 14]);
+
         // Only remove specified stores from region 1.
         debugger
-            .remove_failed_stores(vec![13, 14, 21, 23], Some(vec![1]))
+            .remove_failed_stores(vec![13, 14, 21, 23], Some(vec![1]), false)
             .unwrap();
 
         // 13 and 14 should be removed from region 1.
@@ -1621,14 +1640,18 @@ mod tests {
         assert_eq!(get_region_stores(engine.as_inner(), 2), &[21, 22, 23]);
 
         // Remove specified stores from all regions.
-        debugger.remove_failed_stores(vec![11, 23], None).unwrap();
+        debugger
+            .remove_failed_stores(vec![11, 23], None, false)
+            .unwrap();
 
         assert_eq!(get_region_stores(engine.as_inner(), 1), &[12]);
         assert_eq!(get_region_stores(engine.as_inner(), 2), &[21, 22]);
 
         // Should fail when the store itself is in the failed list.
         init_region_state(engine.as_inner(), 3, &[100, 31, 32, 33]);
-        debugger.remove_failed_stores(vec![100], None).unwrap_err();
+        debugger
+            .remove_failed_stores(vec![100], None, false)
+            .unwrap_err();
     }
 
     #[test]
@@ -21,6 +21,10 @@ pub struct GcConfig {
     /// By default compaction_filter can only works if `cluster_version` is greater than 5.0.0.
     /// Change `compaction_filter_skip_version_check` can enable it by force.
     pub compaction_filter_skip_version_check: bool,
+    /// Also scan the default CF for orphan versions while doing GC. An orphan
+    /// version is referenced by neither a write record nor a pending lock, so
+    /// it can never be read and only wastes space.
+    pub scan_orphan_versions: bool,
 }
 
 impl Default for GcConfig {
@@ -31,6 +35,7 @@ impl Default for GcConfig {
             max_write_bytes_per_sec: ReadableSize(DEFAULT_GC_MAX_WRITE_BYTES_PER_SEC),
             enable_compaction_filter: true,
             compaction_filter_skip_version_check: false,
+            scan_orphan_versions: false,
         }
     }
 }
@@ -429,6 +429,8 @@ impl<S: GcSafePointProvider, R: RegionInfoProvider + 'static, E: KvEngine> GcMan
         // Records how many region we have GC-ed.
         let mut processed_regions = 0;
 
+        let round_start = Instant::now();
+
         info!("gc_worker: auto gc starts"; "safe_point" => self.curr_safe_point());
 
         // The following loop iterates all regions whose leader is on this TiKV and does GC on them.
@@ -468,7 +470,12 @@ impl<S: GcSafePointProvider, R: RegionInfoProvider + 'static, E: KvEngine> GcMan
                 if finished {
                     // We have worked to the end of the TiKV or our progress has reached `end`, and we
                     // don't need to rewind. In this case, the round of GC has finished.
-                    info!("gc_worker: auto gc finishes"; "processed_regions" => processed_regions);
+                    let takes = round_start.saturating_elapsed();
+                    AUTO_GC_ROUND_DURATION_HISTOGRAM.observe(takes.as_secs_f64());
+                    info!("gc_worker: auto gc finishes";
+                        "processed_regions" => processed_regions,
+                        "takes" => ?takes,
+                    );
                     return Ok(());
                 }
             }
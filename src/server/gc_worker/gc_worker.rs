@@ -6,8 +6,11 @@ use std::iter::Peekable;
 use std::mem;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
 use std::vec::IntoIter;
 
+use collections::HashSet;
 use concurrency_manager::ConcurrencyManager;
 use engine_traits::{
     DeleteStrategy, KvEngine, MiscExt, Range, WriteBatch, WriteOptions, CF_DEFAULT, CF_LOCK,
@@ -52,6 +55,10 @@ const GC_LOG_DELETED_VERSION_THRESHOLD: usize = 30;
 pub const GC_MAX_EXECUTING_TASKS: usize = 10;
 const GC_TASK_SLOW_SECONDS: u64 = 30;
 
+/// Retries of each phase of unsafe destroy range on engine errors.
+const UNSAFE_DESTROY_RANGE_MAX_RETRIES: usize = 2;
+const UNSAFE_DESTROY_RANGE_RETRY_INTERVAL: Duration = Duration::from_millis(500);
+
 /// Provides safe point.
 pub trait GcSafePointProvider: Send + 'static {
     fn get_safe_point(&self) -> Result<TimeStamp>;
@@ -259,7 +266,94 @@ where
         Ok(())
     }
 
+    /// Collects the start ts of every version of `key` that is still reachable,
+    /// either through a write record or through a pending lock.
+    fn load_referencing_versions(
+        reader: &mut MvccReader<E::Snap>,
+        key: &Key,
+    ) -> Result<HashSet<TimeStamp>> {
+        let mut referenced = HashSet::default();
+        if let Some(lock) = reader.load_lock(key).map_err(TxnError::from_mvcc)? {
+            referenced.insert(lock.ts);
+        }
+        let mut ts = TimeStamp::max();
+        while let Some((commit_ts, write)) =
+            reader.seek_write(key, ts).map_err(TxnError::from_mvcc)?
+        {
+            referenced.insert(write.start_ts);
+            if commit_ts.is_zero() {
+                break;
+            }
+            ts = commit_ts.prev();
+        }
+        Ok(referenced)
+    }
+
+    /// Scans the default CF for versions that are referenced by neither a write
+    /// record nor a pending lock, and deletes them. Such orphan versions can be
+    /// left behind by a failure after a write CF compaction result is installed
+    /// but before its orphan versions write batch is persisted, and are
+    /// unreachable garbage otherwise.
+    fn gc_orphan_versions(
+        &mut self,
+        start_key: &[u8],
+        end_key: &[u8],
+        safe_point: TimeStamp,
+    ) -> Result<()> {
+        let mut reader = MvccReader::new(
+            self.engine.snapshot_on_kv_engine(start_key, end_key)?,
+            Some(ScanMode::Forward),
+            false,
+        );
+
+        let mut next_version = Some(Key::from_encoded_slice(start_key));
+        let mut deleted_versions = 0;
+        while next_version.is_some() {
+            let (versions, updated_next_version) = reader
+                .scan_default_versions(next_version, self.cfg.batch_keys)
+                .map_err(TxnError::from_mvcc)?;
+            next_version = updated_next_version;
+
+            let mut txn = Self::new_txn();
+            let mut current_key: Option<Key> = None;
+            let mut referenced = HashSet::default();
+            for (key, start_ts) in versions {
+                // A version newer than the safe point may be written by an
+                // ongoing transaction, keep it.
+                if start_ts >= safe_point {
+                    continue;
+                }
+                if current_key.as_ref() != Some(&key) {
+                    referenced = Self::load_referencing_versions(&mut reader, &key)?;
+                    current_key = Some(key.clone());
+                }
+                if !referenced.contains(&start_ts) {
+                    txn.delete_value(key.clone(), start_ts);
+                    deleted_versions += 1;
+                }
+            }
+            Self::flush_txn(txn, &self.limiter, &self.engine)?;
+        }
+
+        if deleted_versions > 0 {
+            info!(
+                "gc worker deletes orphan default versions";
+                "start_key" => log_wrappers::Value::key(start_key),
+                "end_key" => log_wrappers::Value::key(end_key),
+                "versions" => deleted_versions,
+            );
+            GC_COMPACTION_FILTER_ORPHAN_VERSIONS
+                .with_label_values(&["cleaned"])
+                .inc_by(deleted_versions);
+        }
+        self.stats.add(&reader.statistics);
+        Ok(())
+    }
+
     fn gc(&mut self, start_key: &[u8], end_key: &[u8], safe_point: TimeStamp) -> Result<()> {
+        if self.cfg.scan_orphan_versions {
+            self.gc_orphan_versions(start_key, end_key, safe_point)?;
+        }
         if !self.need_gc(start_key, end_key, safe_point) {
             GC_SKIPPED_COUNTER.inc();
             return Ok(());
@@ -401,20 +495,39 @@ where
 
         let cfs = &[CF_LOCK, CF_DEFAULT, CF_WRITE];
 
+        // A transient engine error shouldn't fail the whole request, so every
+        // phase is retried with backoff before giving up.
+        fn with_retry<T, F: FnMut() -> Result<T>>(mut f: F) -> Result<T> {
+            let mut backoff = UNSAFE_DESTROY_RANGE_RETRY_INTERVAL;
+            for _ in 0..UNSAFE_DESTROY_RANGE_MAX_RETRIES {
+                match f() {
+                    Ok(t) => return Ok(t),
+                    Err(e) => {
+                        warn!("unsafe destroy range will retry"; "err" => ?e, "backoff" => ?backoff);
+                        thread::sleep(backoff);
+                        backoff *= 2;
+                    }
+                }
+            }
+            f()
+        }
+
         // First, use DeleteStrategy::DeleteFiles to free as much disk space as possible
         let delete_files_start_time = Instant::now();
         for cf in cfs {
-            local_storage
-                .delete_ranges_cf(
-                    cf,
-                    DeleteStrategy::DeleteFiles,
-                    &[Range::new(&start_data_key, &end_data_key)],
-                )
-                .map_err(|e| {
-                    let e: Error = box_err!(e);
-                    warn!("unsafe destroy range failed at delete_files_in_range_cf"; "err" => ?e);
-                    e
-                })?;
+            with_retry(|| {
+                local_storage
+                    .delete_ranges_cf(
+                        cf,
+                        DeleteStrategy::DeleteFiles,
+                        &[Range::new(&start_data_key, &end_data_key)],
+                    )
+                    .map_err(|e| {
+                        let e: Error = box_err!(e);
+                        warn!("unsafe destroy range failed at delete_files_in_range_cf"; "err" => ?e);
+                        e
+                    })
+            })?;
         }
 
         info!(
@@ -427,28 +540,32 @@ where
         let cleanup_all_start_time = Instant::now();
         for cf in cfs {
             // TODO: set use_delete_range with config here.
-            local_storage
-                .delete_ranges_cf(
-                    cf,
-                    DeleteStrategy::DeleteByKey,
-                    &[Range::new(&start_data_key, &end_data_key)],
-                )
-                .map_err(|e| {
-                    let e: Error = box_err!(e);
-                    warn!("unsafe destroy range failed at delete_all_in_range_cf"; "err" => ?e);
-                    e
-                })?;
-            local_storage
-                .delete_ranges_cf(
-                    cf,
-                    DeleteStrategy::DeleteBlobs,
-                    &[Range::new(&start_data_key, &end_data_key)],
-                )
-                .map_err(|e| {
-                    let e: Error = box_err!(e);
-                    warn!("unsafe destroy range failed at delete_blob_files_in_range"; "err" => ?e);
-                    e
-                })?;
+            with_retry(|| {
+                local_storage
+                    .delete_ranges_cf(
+                        cf,
+                        DeleteStrategy::DeleteByKey,
+                        &[Range::new(&start_data_key, &end_data_key)],
+                    )
+                    .map_err(|e| {
+                        let e: Error = box_err!(e);
+                        warn!("unsafe destroy range failed at delete_all_in_range_cf"; "err" => ?e);
+                        e
+                    })
+            })?;
+            with_retry(|| {
+                local_storage
+                    .delete_ranges_cf(
+                        cf,
+                        DeleteStrategy::DeleteBlobs,
+                        &[Range::new(&start_data_key, &end_data_key)],
+                    )
+                    .map_err(|e| {
+                        let e: Error = box_err!(e);
+                        warn!("unsafe destroy range failed at delete_blob_files_in_range"; "err" => ?e);
+                        e
+                    })
+            })?;
         }
 
         info!(
@@ -470,7 +587,7 @@ where
     }
 
     fn handle_physical_scan_lock(
-        &self,
+        &mut self,
         _: &Context,
         max_ts: TimeStamp,
         start_key: &Key,
@@ -478,12 +595,12 @@ where
     ) -> Result<Vec<LockInfo>> {
         let snap = self
             .engine
-            .snapshot_on_kv_engine(start_key.as_encoded(), &[])
-            .unwrap();
+            .snapshot_on_kv_engine(start_key.as_encoded(), &[])?;
         let mut reader = MvccReader::new(snap, Some(ScanMode::Forward), false);
         let (locks, _) = reader
             .scan_locks(Some(start_key), None, |l| l.ts <= max_ts, limit)
             .map_err(TxnError::from_mvcc)?;
+        self.stats.add(&reader.statistics);
 
         let mut lock_infos = Vec::with_capacity(locks.len());
         for (key, lock) in locks {
@@ -618,6 +735,9 @@ where
             }
             GcTask::OrphanVersions { wb, id } => {
                 info!("handling GcTask::OrphanVersions"; "id" => id);
+                // These deletes are collected by the compaction filter, they
+                // must respect the GC write throttle like other GC writes.
+                self.limiter.blocking_consume(wb.data_size());
                 let mut wopts = WriteOptions::default();
                 wopts.set_sync(true);
                 if let Err(e) = wb.write_opt(&wopts) {
@@ -1375,6 +1495,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_gc_orphan_versions() {
+        let engine = TestEngineBuilder::new().build().unwrap();
+        let prefixed_engine = PrefixedEngine(engine.clone());
+
+        // Values must be long enough to not be inlined as short values,
+        // otherwise nothing is written to the default CF.
+        let long_value = vec![b'x'; 512];
+
+        // A committed version that must be kept.
+        must_prewrite_put(&prefixed_engine, b"k1", &long_value, b"k1", 5);
+        must_commit(&prefixed_engine, b"k1", 5, 6);
+        // A version of a pending lock that must be kept.
+        must_prewrite_put(&prefixed_engine, b"k2", &long_value, b"k2", 10);
+
+        // An orphan version that nothing references.
+        let db = engine.kv_engine().as_inner().clone();
+        let cf = get_cf_handle(&db, CF_DEFAULT).unwrap();
+        let make_raw_key = |key: &[u8], ts: u64| {
+            let mut raw_k = vec![b'z'];
+            let suffix = Key::from_raw(key).append_ts(ts.into());
+            raw_k.extend_from_slice(suffix.as_encoded());
+            raw_k
+        };
+        db.put_cf(cf, &make_raw_key(b"k3", 7), &long_value).unwrap();
+
+        let cfg = GcConfig::default();
+        let mut runner = GcRunner::new(
+            prefixed_engine,
+            RaftStoreBlackHole,
+            Arc::new(VersionTrack::new(cfg.clone())).tracker("gc-worker".to_owned()),
+            cfg,
+        );
+        runner.gc_orphan_versions(b"", b"", TimeStamp::new(100)).unwrap();
+
+        assert!(db.get_cf(cf, &make_raw_key(b"k1", 5)).unwrap().is_some());
+        assert!(db.get_cf(cf, &make_raw_key(b"k2", 10)).unwrap().is_some());
+        assert!(db.get_cf(cf, &make_raw_key(b"k3", 7)).unwrap().is_none());
+    }
+
     #[test]
     fn test_gc_keys_with_region_info_provider() {
         let engine = TestEngineBuilder::new().build().unwrap();
@@ -54,6 +54,12 @@ lazy_static! {
         exponential_buckets(0.0005, 2.0, 20).unwrap() // 0.5ms ~ 524s
     )
     .unwrap();
+    pub static ref WAKE_UP_BATCH_SIZE_HISTOGRAM: Histogram = register_histogram!(
+        "tikv_lock_manager_wake_up_batch_size",
+        "Number of waiters waked up by one released lock",
+        exponential_buckets(1.0, 2.0, 10).unwrap() // 1 ~ 512
+    )
+    .unwrap();
     pub static ref DETECT_DURATION_HISTOGRAM: Histogram = register_histogram!(
         "tikv_lock_manager_detect_duration",
         "Duration of handling detect requests",
@@ -530,6 +530,7 @@ impl WaiterManager {
                 //
                 // NOTE: Actually these waiters are waiting for an unknown transaction.
                 // If there is a deadlock between them, it will be detected after timeout.
+                WAKE_UP_BATCH_SIZE_HISTOGRAM.observe((1 + others.len()) as f64);
                 if others.is_empty() {
                     // Remove the empty entry here.
                     wait_table.remove(lock);
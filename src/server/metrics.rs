@@ -309,6 +309,12 @@ lazy_static! {
         &["type"]
     )
     .unwrap();
+    pub static ref AUTO_GC_ROUND_DURATION_HISTOGRAM: Histogram = register_histogram!(
+        "tikv_gcworker_autogc_round_duration_seconds",
+        "Bucketed histogram of the duration of one round of auto gc",
+        exponential_buckets(1.0, 2.0, 20).unwrap()
+    )
+    .unwrap();
     pub static ref TTL_CHECKER_PROCESSED_REGIONS_GAUGE: IntGauge = register_int_gauge!(
         "tikv_ttl_checker_processed_regions",
         "Processed regions by ttl checker"
@@ -348,6 +354,12 @@ lazy_static! {
         &["type", "store_id"]
     )
     .unwrap();
+    pub static ref RAFT_CLIENT_QUEUE_SIZE_GAUGE_VEC: IntGaugeVec = register_int_gauge_vec!(
+        "tikv_server_raft_client_queue_size",
+        "Pending messages in the send queue of each raft client connection",
+        &["store_id", "conn_id"]
+    )
+    .unwrap();
     pub static ref RAFT_MESSAGE_FLUSH_COUNTER: IntCounter = register_int_counter!(
         "tikv_server_raft_message_flush_total",
         "Total number of raft messages flushed immediately"
@@ -16,6 +16,7 @@ use grpcio::{
 };
 use kvproto::raft_serverpb::{Done, RaftMessage};
 use kvproto::tikvpb::{BatchRaftMessage, TikvClient};
+use prometheus::IntGauge;
 use raft::SnapshotStatus;
 use raftstore::errors::DiscardReason;
 use raftstore::router::RaftStoreRouter;
@@ -142,6 +143,11 @@ trait Buffer {
     ) -> grpcio::Result<()>;
 }
 
+/// Messages below this size are sent uncompressed even when compression is
+/// enabled on the connection, as the CPU spent on compressing them outweighs
+/// the bandwidth saved.
+const MSG_COMPRESSION_THRESHOLD: usize = 4096;
+
 /// A buffer for BatchRaftMessage.
 struct BatchMessageBuffer {
     batch: BatchRaftMessage,
@@ -197,10 +203,10 @@ impl Buffer for BatchMessageBuffer {
     #[inline]
     fn flush(&mut self, sender: &mut ClientCStreamSender<BatchRaftMessage>) -> grpcio::Result<()> {
         let batch = mem::take(&mut self.batch);
-        let res = Pin::new(sender).start_send((
-            batch,
-            WriteFlags::default().buffer_hint(self.overflowing.is_some()),
-        ));
+        let write_flags = WriteFlags::default()
+            .buffer_hint(self.overflowing.is_some())
+            .force_no_compress(self.size < MSG_COMPRESSION_THRESHOLD);
+        let res = Pin::new(sender).start_send((batch, write_flags));
 
         self.size = 0;
         if let Some(more) = self.overflowing.take() {
@@ -742,6 +748,9 @@ struct CachedQueue {
     dirty: bool,
     /// Mark if the connection is full.
     full: bool,
+    /// The depth of the queue, updated on every flush. The handle is cached
+    /// here to avoid label lookups in the send path.
+    queue_size: IntGauge,
 }
 
 /// A raft client that can manages connections correctly.
@@ -831,6 +840,8 @@ where
                 queue: s,
                 dirty: false,
                 full: false,
+                queue_size: RAFT_CLIENT_QUEUE_SIZE_GAUGE_VEC
+                    .with_label_values(&[&store_id.to_string(), &conn_id.to_string()]),
             },
         );
         true
@@ -876,10 +887,36 @@ where
             )
         };
         transport_on_send_store_fp();
+        // Chaos-test hooks. Both points take an optional region id argument to
+        // scope the effect to one region; `delay` and `pause` actions can be
+        // used on them to hold up messages.
+        let transport_drop_fp = || {
+            fail_point!("transport_drop_raft_msg", |id| id
+                .map_or(true, |id| id.parse::<u64>().unwrap() == msg.region_id));
+            false
+        };
+        if transport_drop_fp() {
+            return Err(DiscardReason::Filtered);
+        }
+        let transport_duplicate_fp = || {
+            fail_point!("transport_duplicate_raft_msg", |id| id
+                .map_or(true, |id| id.parse::<u64>().unwrap() == msg.region_id));
+            false
+        };
+        let mut dup_msg = if transport_duplicate_fp() {
+            Some(msg.clone())
+        } else {
+            None
+        };
         loop {
             if let Some(s) = self.cache.get_mut(&(store_id, conn_id)) {
                 match s.queue.push(msg) {
                     Ok(_) => {
+                        if let Some(dup) = dup_msg.take() {
+                            // Best effort, a full queue just drops the
+                            // duplicate.
+                            let _ = s.queue.push(dup);
+                        }
                         if !s.dirty {
                             s.dirty = true;
                             self.need_flush.push((store_id, conn_id));
@@ -919,6 +956,7 @@ where
         for id in &self.full_stores {
             if let Some(s) = self.cache.get_mut(id) {
                 s.full = false;
+                s.queue_size.set(s.queue.len() as i64);
             }
             REPORT_FAILURE_MSG_COUNTER
                 .with_label_values(&["full", &id.0.to_string()])
@@ -940,6 +978,7 @@ where
             if let Some(s) = self.cache.get_mut(id) {
                 if s.dirty {
                     s.dirty = false;
+                    s.queue_size.set(s.queue.len() as i64);
                     s.queue.notify();
                 }
                 continue;
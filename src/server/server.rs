@@ -141,6 +141,11 @@ impl<T: RaftStoreRouter<E::Local> + Unpin, S: StoreAddrResolver + 'static, E: En
             .http2_max_ping_strikes(i32::MAX) // For pings without data from clients.
             .keepalive_time(cfg.value().grpc_keepalive_time.into())
             .keepalive_timeout(cfg.value().grpc_keepalive_timeout.into())
+            // Compresses large responses like coprocessor and backup data if
+            // `server.grpc-compression-type` says so. gRPC negotiates
+            // compression per connection, so it can't be enabled for a subset
+            // of the services only.
+            .default_compression_algorithm(cfg.value().grpc_compression_algorithm())
             .build_args();
         let health_service = HealthService::default();
         let builder = {
@@ -295,6 +300,10 @@ impl<T: RaftStoreRouter<E::Local> + Unpin, S: StoreAddrResolver + 'static, E: En
 
     /// Stops the TiKV server.
     pub fn stop(&mut self) -> Result<()> {
+        // Flip the health status first so that load balancers watching it can
+        // drain connections before the gRPC server goes away.
+        self.health_service
+            .set_serving_status("", ServingStatus::NotServing);
         self.snap_worker.stop();
         if let Some(Either::Right(mut server)) = self.builder_or_server.take() {
             server.shutdown();
@@ -303,8 +312,6 @@ impl<T: RaftStoreRouter<E::Local> + Unpin, S: StoreAddrResolver + 'static, E: En
             let _ = pool.shutdown_background();
         }
         let _ = self.yatp_read_pool.take();
-        self.health_service
-            .set_serving_status("", ServingStatus::NotServing);
         Ok(())
     }
 
@@ -1004,6 +1004,10 @@ impl<T: RaftStoreRouter<E::Local> + 'static, E: Engine, L: LockManager> Tikv for
 
             let mut r = item.batch_resp;
             GRPC_RESP_BATCH_COMMANDS_SIZE.observe(r.request_ids.len() as f64);
+            // Don't set `buffer_hint` even for a full batch: a buffered write
+            // is only flushed by a later write, and `BatchReceiver` can emit a
+            // full batch that drained the channel exactly empty, so a client
+            // waiting on those responses would never see them.
             r.set_transport_layer_load(thread_load.load() as u64);
             GrpcResult::<(BatchCommandsResponse, WriteFlags)>::Ok((
                 r,
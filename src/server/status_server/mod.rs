@@ -410,6 +410,93 @@ where
         }
     }
 
+    pub async fn dump_hot_keys(req: Request<Body>) -> hyper::Result<Response<Body>> {
+        const DEFAULT_LIMIT: usize = 32;
+        let limit: usize = match req.uri().query() {
+            Some(query) => {
+                let query_pairs: HashMap<_, _> =
+                    url::form_urlencoded::parse(query.as_bytes()).collect();
+                match query_pairs.get("limit") {
+                    Some(val) => match val.parse() {
+                        Ok(val) => val,
+                        Err(err) => {
+                            return Ok(StatusServer::err_response(
+                                StatusCode::BAD_REQUEST,
+                                err.to_string(),
+                            ));
+                        }
+                    },
+                    None => DEFAULT_LIMIT,
+                }
+            }
+            None => DEFAULT_LIMIT,
+        };
+
+        let body = match serde_json::to_vec(&crate::storage::hot_keys::dump(limit)) {
+            Ok(body) => body,
+            Err(err) => {
+                return Ok(StatusServer::err_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("fails to json: {}", err),
+                ));
+            }
+        };
+        match Response::builder()
+            .header("content-type", "application/json")
+            .body(hyper::Body::from(body))
+        {
+            Ok(resp) => Ok(resp),
+            Err(err) => Ok(StatusServer::err_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("fails to build response: {}", err),
+            )),
+        }
+    }
+
+    pub async fn dump_engine_stall_events() -> hyper::Result<Response<Body>> {
+        let body = match serde_json::to_vec(&engine_rocks::dump_stall_events()) {
+            Ok(body) => body,
+            Err(err) => {
+                return Ok(StatusServer::err_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("fails to json: {}", err),
+                ));
+            }
+        };
+        match Response::builder()
+            .header("content-type", "application/json")
+            .body(hyper::Body::from(body))
+        {
+            Ok(resp) => Ok(resp),
+            Err(err) => Ok(StatusServer::err_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("fails to build response: {}", err),
+            )),
+        }
+    }
+
+    pub async fn dump_raft_message_drops() -> hyper::Result<Response<Body>> {
+        let body = match serde_json::to_vec(&raftstore::store::dump_dropped_messages()) {
+            Ok(body) => body,
+            Err(err) => {
+                return Ok(StatusServer::err_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("fails to json: {}", err),
+                ));
+            }
+        };
+        match Response::builder()
+            .header("content-type", "application/json")
+            .body(hyper::Body::from(body))
+        {
+            Ok(resp) => Ok(resp),
+            Err(err) => Ok(StatusServer::err_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("fails to build response: {}", err),
+            )),
+        }
+    }
+
     async fn change_log_level(req: Request<Body>) -> hyper::Result<Response<Body>> {
         let mut body = Vec::new();
         req.into_body()
@@ -750,6 +837,17 @@ where
                             (Method::GET, "/debug/pprof/profile") => {
                                 Self::dump_rsperf_to_resp(req).await
                             }
+                            // Hot keys are user data, so the certificate is
+                            // checked like it is for "/region".
+                            (Method::GET, "/debug/hot_keys") => {
+                                Self::dump_hot_keys(req).await
+                            }
+                            (Method::GET, "/debug/engine_stalls") => {
+                                Self::dump_engine_stall_events().await
+                            }
+                            (Method::GET, "/debug/raft_message_drops") => {
+                                Self::dump_raft_message_drops().await
+                            }
                             (Method::GET, "/debug/fail_point") => {
                                 info!("debug fail point API start");
                                 fail_point!("debug_fail_point");
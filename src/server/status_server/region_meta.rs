@@ -172,17 +172,26 @@ pub struct RaftTruncatedState {
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct RaftApplyState {
     pub applied_index: u64,
+    /// The term of the entry at `applied_index`. Together with
+    /// `applied_index` and `commit_index` this lets external tools build
+    /// wait-for-apply predicates.
+    pub applied_index_term: u64,
     pub commit_index: u64,
     pub commit_term: u64,
     pub truncated_state: RaftTruncatedState,
 }
 
+/// A snapshot of a peer's raft state. It is taken on the peer's own raftstore
+/// thread, so all fields are consistent with each other and no staler than
+/// the moment the status server request was handled.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegionMeta {
     pub id: u64,
     pub group_state: GroupState,
-    pub start_key: Vec<u8>,
-    pub end_key: Vec<u8>,
+    /// Hex encoded, as the raw key is not necessarily valid UTF-8.
+    pub start_key: String,
+    /// Hex encoded, as the raw key is not necessarily valid UTF-8.
+    pub end_key: String,
     pub epoch: Epoch,
     pub peers: Vec<RegionPeer>,
     pub merge_state: Option<RegionMergeState>,
@@ -210,8 +219,8 @@ impl RegionMeta {
         Self {
             id: region.get_id(),
             group_state: abstract_peer.group_state(),
-            start_key: start_key.to_owned(),
-            end_key: end_key.to_owned(),
+            start_key: hex::encode_upper(start_key),
+            end_key: hex::encode_upper(end_key),
             epoch: Epoch {
                 conf_ver: epoch.get_conf_ver(),
                 version: epoch.get_version(),
@@ -227,6 +236,7 @@ impl RegionMeta {
             raft_status: abstract_peer.raft_status().into(),
             raft_apply: RaftApplyState {
                 applied_index: apply_state.get_applied_index(),
+                applied_index_term: abstract_peer.applied_index_term(),
                 commit_index: apply_state.get_commit_index(),
                 commit_term: apply_state.get_commit_term(),
                 truncated_state: RaftTruncatedState {
@@ -34,13 +34,29 @@ impl CompactionFilterFactory for TTLCompactionFilterFactory {
         }
 
         let name = CString::new("ttl_compaction_filter").unwrap();
-        let filter = Box::new(TTLCompactionFilter { ts: current });
+        let filter = Box::new(TTLCompactionFilter {
+            ts: current,
+            expired: 0,
+        });
         unsafe { new_compaction_filter_raw(name, filter) }
     }
 }
 
 struct TTLCompactionFilter {
     ts: u64,
+    // How many expired entries this filter has purged. Flushed to the metric
+    // when the filter is dropped to keep the per-key path cheap.
+    expired: u64,
+}
+
+impl Drop for TTLCompactionFilter {
+    fn drop(&mut self) {
+        if self.expired > 0 {
+            TTL_CHECKER_ACTIONS_COUNTER_VEC
+                .with_label_values(&["expire"])
+                .inc_by(self.expired);
+        }
+    }
 }
 
 impl CompactionFilter for TTLCompactionFilter {
@@ -71,6 +87,7 @@ impl CompactionFilter for TTLCompactionFilter {
             return CompactionFilterDecision::Keep;
         }
         if expire_ts <= self.ts {
+            self.expired += 1;
             CompactionFilterDecision::Remove
         } else {
             CompactionFilterDecision::Keep
@@ -33,6 +33,10 @@ const DEFAULT_SCHED_PENDING_WRITE_MB: u64 = 100;
 
 const DEFAULT_RESERVED_SPACE_GB: u64 = 5;
 
+// Disabled by default: a hard value limit must be opted in, otherwise a
+// rolling upgrade could start rejecting writes that used to succeed.
+const DEFAULT_MAX_VALUE_SIZE: usize = 0;
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, OnlineConfig)]
 #[serde(default)]
 #[serde(rename_all = "kebab-case")]
@@ -44,6 +48,9 @@ pub struct Config {
     pub gc_ratio_threshold: f64,
     #[online_config(skip)]
     pub max_key_size: usize,
+    // The largest value accepted by prewrite and raw put. 0 means no limit.
+    #[online_config(skip)]
+    pub max_value_size: usize,
     #[online_config(skip)]
     pub scheduler_concurrency: usize,
     #[online_config(skip)]
@@ -74,6 +81,7 @@ impl Default for Config {
             data_dir: DEFAULT_DATA_DIR.to_owned(),
             gc_ratio_threshold: DEFAULT_GC_RATIO_THRESHOLD,
             max_key_size: DEFAULT_MAX_KEY_SIZE,
+            max_value_size: DEFAULT_MAX_VALUE_SIZE,
             scheduler_concurrency: DEFAULT_SCHED_CONCURRENCY,
             scheduler_worker_pool_size: if cpu_num >= 16.0 { 8 } else { 4 },
             scheduler_pending_write_threshold: ReadableSize::mb(DEFAULT_SCHED_PENDING_WRITE_MB),
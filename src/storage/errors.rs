@@ -47,6 +47,9 @@ pub enum ErrorInner {
     #[error("max key size exceeded, size: {}, limit: {}", .size, .limit)]
     KeyTooLarge { size: usize, limit: usize },
 
+    #[error("max value size exceeded, size: {}, limit: {}", .size, .limit)]
+    ValueTooLarge { size: usize, limit: usize },
+
     #[error("invalid cf name: {0}")]
     InvalidCf(String),
 
@@ -63,6 +66,30 @@ impl From<DeadlineError> for ErrorInner {
     }
 }
 
+impl ErrorInner {
+    pub fn maybe_clone(&self) -> Option<ErrorInner> {
+        match self {
+            ErrorInner::Engine(e) => e.maybe_clone().map(ErrorInner::Engine),
+            ErrorInner::Txn(e) => e.maybe_clone().map(ErrorInner::Txn),
+            ErrorInner::Closed => Some(ErrorInner::Closed),
+            ErrorInner::SchedTooBusy => Some(ErrorInner::SchedTooBusy),
+            ErrorInner::GcWorkerTooBusy => Some(ErrorInner::GcWorkerTooBusy),
+            ErrorInner::KeyTooLarge { size, limit } => Some(ErrorInner::KeyTooLarge {
+                size: *size,
+                limit: *limit,
+            }),
+            ErrorInner::ValueTooLarge { size, limit } => Some(ErrorInner::ValueTooLarge {
+                size: *size,
+                limit: *limit,
+            }),
+            ErrorInner::InvalidCf(cf) => Some(ErrorInner::InvalidCf(cf.clone())),
+            ErrorInner::TTLNotEnabled => Some(ErrorInner::TTLNotEnabled),
+            ErrorInner::DeadlineExceeded => Some(ErrorInner::DeadlineExceeded),
+            ErrorInner::Other(_) | ErrorInner::Io(_) => None,
+        }
+    }
+}
+
 /// Errors for storage module. Wrapper type of `ErrorInner`.
 #[derive(Debug, Error)]
 #[error(transparent)]
@@ -75,6 +102,12 @@ impl From<ErrorInner> for Error {
     }
 }
 
+impl Error {
+    pub fn maybe_clone(&self) -> Option<Error> {
+        self.0.maybe_clone().map(Error::from)
+    }
+}
+
 impl<T: Into<ErrorInner>> From<T> for Error {
     #[inline]
     default fn from(err: T) -> Self {
@@ -94,6 +127,7 @@ impl ErrorCodeExt for Error {
             ErrorInner::SchedTooBusy => error_code::storage::SCHED_TOO_BUSY,
             ErrorInner::GcWorkerTooBusy => error_code::storage::GC_WORKER_TOO_BUSY,
             ErrorInner::KeyTooLarge { .. } => error_code::storage::KEY_TOO_LARGE,
+            ErrorInner::ValueTooLarge { .. } => error_code::storage::VALUE_TOO_LARGE,
             ErrorInner::InvalidCf(_) => error_code::storage::INVALID_CF,
             ErrorInner::TTLNotEnabled => error_code::storage::TTL_NOT_ENABLED,
             ErrorInner::DeadlineExceeded => error_code::storage::DEADLINE_EXCEEDED,
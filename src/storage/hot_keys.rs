@@ -0,0 +1,156 @@
+// Copyright 2021 TiKV Project Authors. Licensed under Apache-2.0.
+
+//! A sampling collector that tracks the approximate hottest keys of the store.
+//!
+//! Read and write paths feed sampled keys into a store level Space-Saving
+//! sketch. The result is served by the status server, so a single hot row can
+//! be identified without external tracing.
+
+use std::sync::Mutex;
+
+use collections::HashMap;
+use rand::Rng;
+
+/// The number of keys tracked by the sketch. Keys beyond the capacity evict
+/// the coldest tracked key, so the capacity only bounds memory usage, not
+/// which keys can become top ones.
+const CAPACITY: usize = 256;
+
+/// Roughly one in `SAMPLE_RATE` accesses is recorded. Counts reported by the
+/// sketch are counts of samples, not of accesses.
+const SAMPLE_RATE: u32 = 128;
+
+struct Counter {
+    count: u64,
+    /// The count inherited from the evicted key when this key started to be
+    /// tracked. The real sample count of the key is within
+    /// `[count - error, count]`.
+    error: u64,
+}
+
+/// An approximate top-K counter backed by the Space-Saving algorithm.
+///
+/// Tracked keys are counted exactly. An untracked key replaces the tracked
+/// key with the minimum count and inherits its count as the estimation error,
+/// which guarantees that a key with more than `total / capacity` samples is
+/// never missed.
+struct SpaceSaving {
+    capacity: usize,
+    counters: HashMap<Vec<u8>, Counter>,
+}
+
+impl SpaceSaving {
+    fn with_capacity(capacity: usize) -> SpaceSaving {
+        assert!(capacity > 0);
+        SpaceSaving {
+            capacity,
+            counters: HashMap::default(),
+        }
+    }
+
+    fn offer(&mut self, key: &[u8]) {
+        if let Some(c) = self.counters.get_mut(key) {
+            c.count += 1;
+            return;
+        }
+        if self.counters.len() < self.capacity {
+            self.counters
+                .insert(key.to_vec(), Counter { count: 1, error: 0 });
+            return;
+        }
+        let min_key = self
+            .counters
+            .iter()
+            .min_by_key(|(_, c)| c.count)
+            .map(|(k, _)| k.clone())
+            .unwrap();
+        let min_count = self.counters.remove(&min_key).unwrap().count;
+        self.counters.insert(
+            key.to_vec(),
+            Counter {
+                count: min_count + 1,
+                error: min_count,
+            },
+        );
+    }
+
+    fn top(&self, limit: usize) -> Vec<HotKey> {
+        let mut keys: Vec<_> = self
+            .counters
+            .iter()
+            .map(|(k, c)| HotKey {
+                key: hex::encode_upper(k),
+                count: c.count,
+                error: c.error,
+            })
+            .collect();
+        keys.sort_by(|a, b| b.count.cmp(&a.count));
+        keys.truncate(limit);
+        keys
+    }
+}
+
+/// A hot key entry reported by the status server.
+#[derive(Serialize)]
+pub struct HotKey {
+    /// Hex encoded key. It's an encoded data key without the key prefix.
+    pub key: String,
+    pub count: u64,
+    pub error: u64,
+}
+
+lazy_static! {
+    static ref HOT_KEYS: Mutex<SpaceSaving> = Mutex::new(SpaceSaving::with_capacity(CAPACITY));
+}
+
+/// Records one access to the key with sampling.
+///
+/// It is called in read and write hot paths, so most calls only draw a random
+/// number. Contention on the sketch is skipped instead of waited for.
+pub fn maybe_sample(key: &[u8]) {
+    if !rand::thread_rng().gen_ratio(1, SAMPLE_RATE) {
+        return;
+    }
+    if let Ok(mut sketch) = HOT_KEYS.try_lock() {
+        sketch.offer(key);
+    }
+}
+
+/// Returns up to `limit` hottest keys, ordered by count descending.
+pub fn dump(limit: usize) -> Vec<HotKey> {
+    HOT_KEYS.lock().unwrap().top(limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_space_saving() {
+        let mut sketch = SpaceSaving::with_capacity(2);
+        for _ in 0..3 {
+            sketch.offer(b"a");
+        }
+        sketch.offer(b"b");
+        let top = sketch.top(10);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].key, hex::encode_upper(b"a"));
+        assert_eq!(top[0].count, 3);
+        assert_eq!(top[0].error, 0);
+
+        // "c" evicts "b" and inherits its count as error.
+        sketch.offer(b"c");
+        let top = sketch.top(10);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[1].key, hex::encode_upper(b"c"));
+        assert_eq!(top[1].count, 2);
+        assert_eq!(top[1].error, 1);
+
+        // A key hotter than the eviction threshold is never missed.
+        for _ in 0..10 {
+            sketch.offer(b"d");
+        }
+        let top = sketch.top(1);
+        assert_eq!(top[0].key, hex::encode_upper(b"d"));
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright 2021 TiKV Project Authors. Licensed under Apache-2.0.
+
+//! Key-space definitions for API v2.
+//!
+//! With API v2 enabled, keys are prefix-encoded so that RawKV, TxnKV and TiDB
+//! data live in disjoint key spaces and can safely coexist in one cluster:
+//!
+//! - `r` prefixes RawKV keys,
+//! - `x` prefixes TxnKV keys,
+//! - `m` and `t` are reserved for TiDB data.
+//!
+//! Request validation against the store's API version additionally requires
+//! the version to be carried in the request context, so it is wired up where
+//! requests enter the storage layer.
+
+/// The key mode inferred from the first byte of an encoded key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyMode {
+    /// Raw key.
+    Raw,
+    /// Transaction key.
+    Txn,
+    /// TiDB key.
+    ///
+    /// It doesn't mean that the key is certainly written by TiDB, but instead
+    /// means that the key matches the definition of TiDB key in API v2.
+    TiDB,
+    /// Unrecognised key mode.
+    Unknown,
+}
+
+pub const RAW_KEY_PREFIX: u8 = b'r';
+pub const TXN_KEY_PREFIX: u8 = b'x';
+pub const TIDB_META_KEY_PREFIX: u8 = b'm';
+pub const TIDB_TABLE_KEY_PREFIX: u8 = b't';
+
+impl KeyMode {
+    /// Parses the key mode of a single key.
+    pub fn parse_from_key(key: &[u8]) -> KeyMode {
+        match key.first() {
+            Some(&RAW_KEY_PREFIX) => KeyMode::Raw,
+            Some(&TXN_KEY_PREFIX) => KeyMode::Txn,
+            Some(&TIDB_META_KEY_PREFIX) | Some(&TIDB_TABLE_KEY_PREFIX) => KeyMode::TiDB,
+            _ => KeyMode::Unknown,
+        }
+    }
+
+    /// Parses the key mode of a key range `[start_key, end_key)`.
+    ///
+    /// Returns `Unknown` unless the whole range is covered by exactly one key
+    /// space, so that a request can never touch keys of another mode by
+    /// passing a loose range.
+    pub fn parse_from_range(start_key: &[u8], end_key: &[u8]) -> KeyMode {
+        let mode = KeyMode::parse_from_key(start_key);
+        if mode == KeyMode::Unknown {
+            return KeyMode::Unknown;
+        }
+        // An empty end key means "up to the maximum key", which always crosses
+        // key spaces.
+        if end_key.is_empty() {
+            return KeyMode::Unknown;
+        }
+        // The range is within one key space iff both keys sit under the same
+        // prefix byte, or the end key is exactly the upper bound of that
+        // prefix. Comparing modes is not enough: TiDB owns two disjoint
+        // prefixes (`m` and `t`), and a range from one to the other would
+        // cover the whole Raw key space in between.
+        let bound = [start_key[0] + 1];
+        if end_key[0] == start_key[0] || end_key <= &bound[..] {
+            mode
+        } else {
+            KeyMode::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_from_key() {
+        assert_eq!(KeyMode::parse_from_key(b"rkey"), KeyMode::Raw);
+        assert_eq!(KeyMode::parse_from_key(b"xkey"), KeyMode::Txn);
+        assert_eq!(KeyMode::parse_from_key(b"mkey"), KeyMode::TiDB);
+        assert_eq!(KeyMode::parse_from_key(b"tkey"), KeyMode::TiDB);
+        assert_eq!(KeyMode::parse_from_key(b"okey"), KeyMode::Unknown);
+        assert_eq!(KeyMode::parse_from_key(b""), KeyMode::Unknown);
+    }
+
+    #[test]
+    fn test_parse_from_range() {
+        assert_eq!(KeyMode::parse_from_range(b"ra", b"rz"), KeyMode::Raw);
+        // The bound of the raw key space is still in range.
+        assert_eq!(KeyMode::parse_from_range(b"ra", b"s"), KeyMode::Raw);
+        assert_eq!(KeyMode::parse_from_range(b"xa", b"xz"), KeyMode::Txn);
+        // Ranges crossing key spaces don't belong to either.
+        assert_eq!(KeyMode::parse_from_range(b"ra", b"xz"), KeyMode::Unknown);
+        // TiDB owns two disjoint prefixes; a range spanning from one to the
+        // other crosses the Raw key space and is not a TiDB range.
+        assert_eq!(KeyMode::parse_from_range(b"ma", b"tz"), KeyMode::Unknown);
+        assert_eq!(KeyMode::parse_from_range(b"ta", b"tz"), KeyMode::TiDB);
+        assert_eq!(KeyMode::parse_from_range(b"ma", b"n"), KeyMode::TiDB);
+        assert_eq!(KeyMode::parse_from_range(b"ra", b""), KeyMode::Unknown);
+        assert_eq!(KeyMode::parse_from_range(b"", b"rz"), KeyMode::Unknown);
+    }
+}
@@ -64,6 +64,41 @@ pub fn tls_collect_scan_details(cmd: CommandKind, stats: &Statistics) {
             .or_insert_with(Default::default)
             .add(stats);
     });
+    // Feed the lock cf tombstone traversal cost back to raftstore, which
+    // compacts the lock cf once scans have skipped enough of them.
+    let lock_tombstones = stats.lock.next_tombstone
+        + stats.lock.prev_tombstone
+        + stats.lock.seek_tombstone
+        + stats.lock.seek_for_prev_tombstone;
+    if lock_tombstones > 0 {
+        raftstore::store::add_lock_cf_tombstones_scanned(lock_tombstones as u64);
+    }
+}
+
+/// Records the flow of a read served by this peer as a follower, classified as
+/// a replica read or a stale read. Reads served as a leader are not counted.
+///
+/// Follower read flow already reaches PD through the per-peer read stats in
+/// store heartbeats, these counters break it down by the kind of read.
+pub fn tls_collect_follower_read_flow(
+    replica_read: bool,
+    stale_read: bool,
+    statistics: &Statistics,
+) {
+    if !replica_read && !stale_read {
+        return;
+    }
+    let bytes =
+        (statistics.write.flow_stats.read_bytes + statistics.data.flow_stats.read_bytes) as u64;
+    let keys =
+        (statistics.write.flow_stats.read_keys + statistics.data.flow_stats.read_keys) as u64;
+    if stale_read {
+        FOLLOWER_READ_BYTES_VEC.stale.inc_by(bytes);
+        FOLLOWER_READ_KEYS_VEC.stale.inc_by(keys);
+    } else {
+        FOLLOWER_READ_BYTES_VEC.replica.inc_by(bytes);
+        FOLLOWER_READ_KEYS_VEC.replica.inc_by(keys);
+    }
 }
 
 pub fn tls_collect_read_flow(region_id: u64, statistics: &Statistics) {
@@ -197,6 +232,11 @@ make_auto_flush_static_metric! {
         unlocked,
     }
 
+    pub label_enum FollowerReadKind {
+        replica,
+        stale,
+    }
+
     pub struct CommandScanDetails: LocalIntCounter {
         "req" => CommandKind,
         "cf" => GcKeysCF,
@@ -244,6 +284,10 @@ make_auto_flush_static_metric! {
         "type" => CommandKind,
         "result" => CheckMemLockResult,
     }
+
+    pub struct FollowerReadFlowVec: LocalIntCounter {
+        "kind" => FollowerReadKind,
+    }
 }
 
 impl From<ServerGcKeysCF> for GcKeysCF {
@@ -492,4 +536,20 @@ lazy_static! {
     .unwrap();
     pub static ref CHECK_MEM_LOCK_DURATION_HISTOGRAM_VEC: CheckMemLockHistogramVec =
         auto_flush_from!(CHECK_MEM_LOCK_DURATION_HISTOGRAM, CheckMemLockHistogramVec);
+    pub static ref FOLLOWER_READ_BYTES: IntCounterVec = register_int_counter_vec!(
+        "tikv_storage_follower_read_bytes",
+        "Total bytes of reads served by this peer as a follower",
+        &["kind"]
+    )
+    .unwrap();
+    pub static ref FOLLOWER_READ_BYTES_VEC: FollowerReadFlowVec =
+        auto_flush_from!(FOLLOWER_READ_BYTES, FollowerReadFlowVec);
+    pub static ref FOLLOWER_READ_KEYS: IntCounterVec = register_int_counter_vec!(
+        "tikv_storage_follower_read_keys",
+        "Total keys of reads served by this peer as a follower",
+        &["kind"]
+    )
+    .unwrap();
+    pub static ref FOLLOWER_READ_KEYS_VEC: FollowerReadFlowVec =
+        auto_flush_from!(FOLLOWER_READ_KEYS, FollowerReadFlowVec);
 }
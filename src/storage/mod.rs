@@ -36,6 +36,8 @@
 
 pub mod config;
 pub mod errors;
+pub mod hot_keys;
+pub mod key_mode;
 pub mod kv;
 pub mod lock_manager;
 pub(crate) mod metrics;
@@ -139,6 +141,7 @@ pub struct Storage<E: Engine, L: LockManager> {
 
     // Fields below are storage configurations.
     max_key_size: usize,
+    max_value_size: usize,
 
     enable_ttl: bool,
 }
@@ -158,6 +161,7 @@ impl<E: Engine, L: LockManager> Clone for Storage<E, L> {
             read_pool: self.read_pool.clone(),
             refs: self.refs.clone(),
             max_key_size: self.max_key_size,
+            max_value_size: self.max_value_size,
             concurrency_manager: self.concurrency_manager.clone(),
             enable_ttl: self.enable_ttl,
         }
@@ -196,6 +200,31 @@ macro_rules! check_key_size {
     };
 }
 
+macro_rules! check_value_size {
+    ($value_iter: expr, $max_value_size: expr, $callback: ident) => {
+        if $max_value_size > 0 {
+            for v in $value_iter {
+                let value_size = v.len();
+                if value_size > $max_value_size {
+                    $callback(Err(Error::from(ErrorInner::ValueTooLarge {
+                        size: value_size,
+                        limit: $max_value_size,
+                    })));
+                    return Ok(());
+                }
+            }
+        }
+    };
+}
+
+/// The value carried by a mutation, if it writes one.
+fn mutation_value(m: &Mutation) -> Option<&Value> {
+    match m {
+        Mutation::Put((_, ref v)) | Mutation::Insert((_, ref v)) => Some(v),
+        _ => None,
+    }
+}
+
 impl<E: Engine, L: LockManager> Storage<E, L> {
     /// Create a `Storage` from given engine.
     pub fn from_engine<R: FlowStatsReporter>(
@@ -227,6 +256,7 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
             concurrency_manager,
             refs: Arc::new(atomic::AtomicUsize::new(1)),
             max_key_size: config.max_key_size,
+            max_value_size: config.max_value_size,
             enable_ttl: config.enable_ttl,
         })
     }
@@ -341,6 +371,7 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                     false,
                     QueryKind::Get,
                 );
+                hot_keys::maybe_sample(key.as_encoded());
 
                 KV_COMMAND_COUNTER_VEC_STATIC.get(CMD).inc();
                 SCHED_COMMANDS_PRI_COUNTER_VEC_STATIC
@@ -386,6 +417,11 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
 
                     metrics::tls_collect_scan_details(CMD, &statistics);
                     metrics::tls_collect_read_flow(ctx.get_region_id(), &statistics);
+                    metrics::tls_collect_follower_read_flow(
+                        ctx.get_replica_read(),
+                        ctx.get_stale_read(),
+                        &statistics,
+                    );
                     SCHED_PROCESSING_READ_HISTOGRAM_STATIC
                         .get(CMD)
                         .observe(begin_instant.saturating_elapsed_secs());
@@ -437,6 +473,11 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                     .observe(requests.len() as f64);
                 let command_duration = tikv_util::time::Instant::now_coarse();
                 let read_id = Some(ThreadReadId::new());
+                // All requests in a batch share the replica_read and stale_read flags.
+                let (replica_read, stale_read) = {
+                    let ctx = requests[0].get_context();
+                    (ctx.get_replica_read(), ctx.get_stale_read())
+                };
                 let mut statistics = Statistics::default();
                 let mut req_snaps = vec![];
 
@@ -458,6 +499,31 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                     let fill_cache = !ctx.get_not_fill_cache();
                     let bypass_locks = TsSet::vec_from_u64s(ctx.take_resolved_locks());
                     let region_id = ctx.get_region_id();
+                    // The request context decides which snapshot is taken and which
+                    // errors (e.g. `EpochNotMatch`) the request can observe, so it
+                    // must be part of the deduplication key.
+                    let ctx_key = (
+                        region_id,
+                        ctx.get_region_epoch().clone(),
+                        ctx.get_replica_read(),
+                        ctx.get_stale_read(),
+                    );
+
+                    // TiDB may batch identical point gets into one request, e.g. for
+                    // a point plan with duplicate values in an IN list. Identical
+                    // gets are executed only once and the result is consumed for
+                    // every matching request.
+                    if let Some((.., ids)) = req_snaps.iter_mut().find(|(_, k, ts, iso, fc, bl, ck, _)| {
+                        *k == key
+                            && *ts == start_ts
+                            && *iso == isolation_level
+                            && *fc == fill_cache
+                            && *bl == bypass_locks
+                            && *ck == ctx_key
+                    }) {
+                        ids.push(id);
+                        continue;
+                    }
 
                     let snap_ctx = match prepare_snap_ctx(
                         &ctx,
@@ -489,8 +555,8 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                         isolation_level,
                         fill_cache,
                         bypass_locks,
-                        region_id,
-                        id,
+                        ctx_key,
+                        vec![id],
                     ));
                 }
                 Self::with_tls_engine(|engine| engine.release_snapshot());
@@ -502,10 +568,11 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                         isolation_level,
                         fill_cache,
                         bypass_locks,
-                        region_id,
-                        id,
+                        (region_id, ..),
+                        ids,
                     ) = req_snap;
-                    match snap.await {
+                    hot_keys::maybe_sample(key.as_encoded());
+                    let res = match snap.await {
                         Ok(snapshot) => {
                             match PointGetterBuilder::new(snapshot, start_ts)
                                 .fill_cache(fill_cache)
@@ -519,26 +586,38 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                                     let v = point_getter.get(&key);
                                     let stat = point_getter.take_statistics();
                                     metrics::tls_collect_read_flow(region_id, &stat);
-                                    statistics.add(&stat);
-                                    consumer.consume(
-                                        id,
-                                        v.map_err(|e| Error::from(txn::Error::from(e)))
-                                            .map(|v| (v, stat, perf_statistics.delta())),
-                                        begin_instant,
-                                    );
-                                }
-                                Err(e) => {
-                                    consumer.consume(
-                                        id,
-                                        Err(Error::from(txn::Error::from(e))),
-                                        begin_instant,
+                                    metrics::tls_collect_follower_read_flow(
+                                        replica_read,
+                                        stale_read,
+                                        &stat,
                                     );
+                                    statistics.add(&stat);
+                                    v.map_err(|e| Error::from(txn::Error::from(e)))
+                                        .map(|v| (v, stat, perf_statistics.delta()))
                                 }
+                                Err(e) => Err(Error::from(txn::Error::from(e))),
                             }
                         }
-                        Err(e) => {
-                            consumer.consume(id, Err(e), begin_instant);
-                        }
+                        Err(e) => Err(e),
+                    };
+                    let last = ids.len() - 1;
+                    let mut res = Some(res);
+                    for (i, id) in ids.into_iter().enumerate() {
+                        let res = if i == last {
+                            res.take().unwrap()
+                        } else {
+                            // Deduplicated requests must observe the same result.
+                            match res.as_ref().unwrap() {
+                                Ok((v, stat, delta)) => Ok((v.clone(), stat.clone(), *delta)),
+                                // Not every error is cloneable (e.g. engine or IO
+                                // errors); fall back to an opaque error carrying the
+                                // original message instead of panicking.
+                                Err(e) => Err(e
+                                    .maybe_clone()
+                                    .unwrap_or_else(|| ErrorInner::Other(box_err!("{}", e)).into())),
+                            }
+                        };
+                        consumer.consume(id, res, begin_instant);
                     }
                 }
                 metrics::tls_collect_scan_details(CMD, &statistics);
@@ -641,6 +720,11 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
 
                     metrics::tls_collect_scan_details(CMD, &statistics);
                     metrics::tls_collect_read_flow(ctx.get_region_id(), &statistics);
+                    metrics::tls_collect_follower_read_flow(
+                        ctx.get_replica_read(),
+                        ctx.get_stale_read(),
+                        &statistics,
+                    );
                     SCHED_PROCESSING_READ_HISTOGRAM_STATIC
                         .get(CMD)
                         .observe(begin_instant.saturating_elapsed_secs());
@@ -778,6 +862,11 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                     let statistics = scanner.take_statistics();
                     metrics::tls_collect_scan_details(CMD, &statistics);
                     metrics::tls_collect_read_flow(ctx.get_region_id(), &statistics);
+                    metrics::tls_collect_follower_read_flow(
+                        ctx.get_replica_read(),
+                        ctx.get_stale_read(),
+                        &statistics,
+                    );
                     SCHED_PROCESSING_READ_HISTOGRAM_STATIC
                         .get(CMD)
                         .observe(begin_instant.saturating_elapsed_secs());
@@ -964,6 +1053,11 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                     self.max_key_size,
                     callback
                 );
+                check_value_size!(
+                    mutations.iter().filter_map(mutation_value),
+                    self.max_value_size,
+                    callback
+                );
             }
             Command::PrewritePessimistic(PrewritePessimistic { mutations, .. }) => {
                 check_key_size!(
@@ -971,6 +1065,11 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                     self.max_key_size,
                     callback
                 );
+                check_value_size!(
+                    mutations.iter().filter_map(|(m, _)| mutation_value(m)),
+                    self.max_value_size,
+                    callback
+                );
             }
             Command::AcquirePessimisticLock(AcquirePessimisticLock { keys, .. }) => {
                 check_key_size!(
@@ -1280,6 +1379,7 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
         callback: Callback<()>,
     ) -> Result<()> {
         check_key_size!(Some(&key).into_iter(), self.max_key_size, callback);
+        check_value_size!(Some(&value).into_iter(), self.max_value_size, callback);
         let mut m = Modify::Put(Self::rawkv_cf(&cf)?, Key::from_encoded(key), value);
         if self.enable_ttl {
             let expire_ts = convert_to_expire_ts(ttl);
@@ -1316,6 +1416,11 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
             self.max_key_size,
             callback
         );
+        check_value_size!(
+            pairs.iter().map(|(_, ref v)| v),
+            self.max_value_size,
+            callback
+        );
 
         if !self.enable_ttl && ttl != 0 {
             return Err(Error::from(ErrorInner::TTLNotEnabled));
@@ -1371,6 +1476,11 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
     }
 
     /// Delete all raw keys in [`start_key`, `end_key`).
+    ///
+    /// The range is confined to the data keyspace by the raftstore key prefix,
+    /// so it can never span the meta or system prefixes, and large spans are
+    /// handled with delete-files-in-range when the command is applied. A
+    /// request level confirmation flag would need a new kvproto field.
     pub fn raw_delete_range(
         &self,
         ctx: Context,
@@ -1387,6 +1497,17 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
             callback
         );
 
+        // Range deletions destroy data in bulk, so leave an audit trail of
+        // who asked for what.
+        info!(
+            "raw_delete_range";
+            "cf" => &cf,
+            "start_key" => log_wrappers::Value::key(&start_key),
+            "end_key" => log_wrappers::Value::key(&end_key),
+            "region_id" => ctx.get_region_id(),
+            "peer" => ctx.get_peer().get_store_id()
+        );
+
         let cf = Self::rawkv_cf(&cf)?;
         let start_key = Key::from_encoded(start_key);
         let end_key = Key::from_encoded(end_key);
@@ -1442,6 +1563,7 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
     ///
     /// If `key_only` is true, the value
     /// corresponding to the key will not be read out. Only scanned keys will be returned.
+    ///
     pub fn raw_scan(
         &self,
         ctx: Context,
@@ -1492,6 +1614,7 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                     let end_key = end_key.map(Key::from_encoded);
 
                     let mut statistics = Statistics::default();
+                    let fill_cache = !ctx.get_not_fill_cache();
                     let result = if reverse_scan {
                         store
                             .reverse_raw_scan(
@@ -1500,6 +1623,7 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                                 end_key.as_ref(),
                                 limit,
                                 &mut statistics,
+                                fill_cache,
                                 key_only,
                             )
                             .await
@@ -1511,6 +1635,7 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                                 end_key.as_ref(),
                                 limit,
                                 &mut statistics,
+                                fill_cache,
                                 key_only,
                             )
                             .await
@@ -1543,6 +1668,33 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
         }
     }
 
+    /// Scan all raw keys starting with `prefix`, i.e. the range
+    /// [`prefix`, `keys::next_key(prefix)`).
+    ///
+    /// The upper bound is computed here instead of by the client, so a prefix
+    /// scan can not accidentally leak into the next key space through a
+    /// mis-constructed end key. Keeping both iterator bounds within one
+    /// prefix also lets RocksDB consult the prefix bloom filter on column
+    /// families that configure a prefix extractor.
+    ///
+    /// This function scans at most `limit` keys.
+    pub fn raw_scan_prefix(
+        &self,
+        ctx: Context,
+        cf: String,
+        prefix: Vec<u8>,
+        limit: usize,
+        key_only: bool,
+    ) -> impl Future<Output = Result<Vec<Result<KvPair>>>> {
+        let end_key = match keys::next_key(&prefix) {
+            // An empty next key means the prefix is all `0xff`, in which case
+            // the scan is unbounded on the right.
+            k if k.is_empty() => None,
+            k => Some(k),
+        };
+        self.raw_scan(ctx, cf, prefix, end_key, limit, key_only, false)
+    }
+
     /// Scan raw keys in multiple ranges in a batch.
     pub fn raw_batch_scan(
         &self,
@@ -1602,6 +1754,7 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                         } else {
                             Some(Key::from_encoded(end_key))
                         };
+                        let fill_cache = !ctx.get_not_fill_cache();
                         let pairs: Vec<Result<KvPair>> = if reverse_scan {
                             store
                                 .reverse_raw_scan(
@@ -1610,6 +1763,7 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                                     end_key.as_ref(),
                                     each_limit,
                                     &mut statistics,
+                                    fill_cache,
                                     key_only,
                                 )
                                 .await
@@ -1621,6 +1775,7 @@ impl<E: Engine, L: LockManager> Storage<E, L> {
                                     end_key.as_ref(),
                                     each_limit,
                                     &mut statistics,
+                                    fill_cache,
                                     key_only,
                                 )
                                 .await
@@ -2259,6 +2414,7 @@ mod tests {
                     extra_op: ExtraOp::Noop,
                     statistics: &mut Statistics::default(),
                     async_apply_prewrite: false,
+                    txn_status_cache: None,
                 },
             )
             .unwrap();
@@ -3154,6 +3310,29 @@ mod tests {
                 Some(b"bb".to_vec())
             ]
         );
+
+        // Identical gets are deduplicated but still answered per request.
+        let consumer = GetConsumer::new();
+        block_on(storage.batch_get_command(
+            vec![
+                create_get_request(b"a", 5),
+                create_get_request(b"a", 5),
+                create_get_request(b"a", 1),
+            ],
+            vec![1, 2, 3],
+            consumer.clone(),
+            Instant::now(),
+        ))
+        .unwrap();
+        let x: Vec<Option<Vec<u8>>> = consumer
+            .take_data()
+            .into_iter()
+            .map(|x| x.unwrap())
+            .collect();
+        assert_eq!(
+            x,
+            vec![Some(b"aa".to_vec()), Some(b"aa".to_vec()), None]
+        );
     }
 
     #[test]
@@ -3956,6 +4135,69 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_raw_scan_prefix() {
+        let storage = TestStorageBuilder::new(DummyLockManager {}, false)
+            .build()
+            .unwrap();
+        let (tx, rx) = channel();
+
+        let test_data = vec![
+            (b"a".to_vec(), b"aa".to_vec()),
+            (b"a1".to_vec(), b"aa11".to_vec()),
+            (b"a2".to_vec(), b"aa22".to_vec()),
+            (b"b".to_vec(), b"bb".to_vec()),
+            (b"b1".to_vec(), b"bb11".to_vec()),
+            (b"\xff\xff1".to_vec(), b"ff11".to_vec()),
+            (b"\xff\xff2".to_vec(), b"ff22".to_vec()),
+        ];
+        storage
+            .raw_batch_put(
+                Context::default(),
+                "".to_string(),
+                test_data.clone(),
+                0,
+                expect_ok_callback(tx, 0),
+            )
+            .unwrap();
+        rx.recv().unwrap();
+
+        // Only keys under the prefix are returned, the bound is computed
+        // internally.
+        let results: Vec<Option<KvPair>> = test_data[..3]
+            .iter()
+            .map(|(k, v)| Some((k.clone(), v.clone())))
+            .collect();
+        expect_multi_values(
+            results,
+            block_on(storage.raw_scan_prefix(
+                Context::default(),
+                "".to_string(),
+                b"a".to_vec(),
+                20,
+                false,
+            ))
+            .unwrap(),
+        );
+        // An all-0xff prefix has no successor key; the scan is unbounded on
+        // the right instead of ending early.
+        let results: Vec<Option<KvPair>> = test_data[5..]
+            .iter()
+            .map(|(k, v)| Some((k.clone(), v.clone())))
+            .collect();
+        expect_multi_values(
+            results,
+            block_on(storage.raw_scan_prefix(
+                Context::default(),
+                "".to_string(),
+                b"\xff\xff".to_vec(),
+                20,
+                false,
+            ))
+            .unwrap(),
+        );
+    }
+
     #[test]
     fn test_raw_scan() {
         test_raw_scan_impl(false)
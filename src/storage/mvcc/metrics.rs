@@ -25,9 +25,22 @@ make_static_metric! {
         rollback,
         update_ts,
         get_commit_info,
+        get_commit_info_from_cache,
         pessimistic_rollback,
     }
 
+    pub label_enum MvccTxnCommandKind {
+        prewrite,
+        commit,
+    }
+
+    pub label_enum MvccTxnModeKind {
+        optimistic,
+        pessimistic,
+        async_commit,
+        one_pc,
+    }
+
     pub struct MvccConflictCounterVec: IntCounter {
         "type" => MvccConflictKind,
     }
@@ -39,6 +52,11 @@ make_static_metric! {
     pub struct MvccCheckTxnStatusCounterVec: IntCounter {
         "type" => MvccCheckTxnStatusKind,
     }
+
+    pub struct MvccTxnModeCounterVec: IntCounter {
+        "command" => MvccTxnCommandKind,
+        "mode" => MvccTxnModeKind,
+    }
 }
 
 lazy_static! {
@@ -87,4 +105,15 @@ lazy_static! {
         )
         .unwrap()
     };
+    pub static ref MVCC_TXN_MODE_COUNTER_VEC: MvccTxnModeCounterVec = {
+        register_static_int_counter_vec!(
+            MvccTxnModeCounterVec,
+            "tikv_storage_mvcc_txn_mode_counter",
+            "Total number of keys prewritten or committed in each transaction mode. \
+             async_commit and one_pc are counted in addition to the optimistic or \
+             pessimistic mode of the transaction",
+            &["command", "mode"]
+        )
+        .unwrap()
+    };
 }
@@ -77,6 +77,11 @@ impl<S: Snapshot> PointGetterBuilder<S> {
 
     /// Set a set to locks that the reading process can bypass.
     ///
+    /// This carries the `resolved_locks` from the request context: locks the
+    /// client has already resolved (it checked their status and knows they
+    /// commit after the read ts or roll back), so hitting one doesn't return
+    /// `KeyIsLocked` and lock resolution never blocks the read.
+    ///
     /// Defaults to none.
     #[inline]
     pub fn bypass_locks(mut self, locks: TsSet) -> Self {
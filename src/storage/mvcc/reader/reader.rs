@@ -384,11 +384,12 @@ impl<S: EngineSnapshot> MvccReader<S> {
             return Ok((vec![], false));
         }
         let mut locks = Vec::with_capacity(limit);
+        let mut has_remain = false;
         while cursor.valid()? {
             let key = Key::from_encoded_slice(cursor.key(&mut self.statistics.lock));
             if let Some(end) = end {
                 if key >= *end {
-                    return Ok((locks, false));
+                    break;
                 }
             }
 
@@ -396,14 +397,15 @@ impl<S: EngineSnapshot> MvccReader<S> {
             if filter(&lock) {
                 locks.push((key, lock));
                 if limit > 0 && locks.len() == limit {
-                    return Ok((locks, true));
+                    // There may be more locks after the limit is reached.
+                    has_remain = true;
+                    break;
                 }
             }
             cursor.next(&mut self.statistics.lock);
         }
         self.statistics.lock.processed_keys += locks.len();
-        // If we reach here, `cursor.valid()` is `false`, so there MUST be no more locks.
-        Ok((locks, false))
+        Ok((locks, has_remain))
     }
 
     pub fn scan_keys(
@@ -457,6 +459,34 @@ impl<S: EngineSnapshot> MvccReader<S> {
         Ok(v)
     }
 
+    // Scan versions in CF_DEFAULT. Returns the user key and the start ts of at
+    // most `limit` versions, and a key that the next scan can resume from.
+    pub fn scan_default_versions(
+        &mut self,
+        start: Option<Key>,
+        limit: usize,
+    ) -> Result<(Vec<(Key, TimeStamp)>, Option<Key>)> {
+        self.create_data_cursor()?;
+        let cursor = self.data_cursor.as_mut().unwrap();
+        let mut ok = match start {
+            Some(ref x) => cursor.near_seek(x, &mut self.statistics.data)?,
+            None => cursor.seek_to_first(&mut self.statistics.data),
+        };
+        let mut versions = vec![];
+        while ok {
+            let current = cursor.key(&mut self.statistics.data);
+            if versions.len() >= limit {
+                self.statistics.data.processed_keys += versions.len();
+                return Ok((versions, Some(Key::from_encoded_slice(current))));
+            }
+            let ts = Key::decode_ts_from(current)?;
+            versions.push((Key::from_encoded_slice(current).truncate_ts()?, ts));
+            ok = cursor.next(&mut self.statistics.data);
+        }
+        self.statistics.data.processed_keys += versions.len();
+        Ok((versions, None))
+    }
+
     /// Read the old value for key for CDC.
     /// `prev_write` stands for the previous write record of the key
     /// it must be read in the caller and be passed in for optimization
@@ -1416,6 +1446,8 @@ pub mod tests {
                 .unwrap();
             assert_eq!(res.0, expect_res);
             assert_eq!(res.1, expect_is_remain);
+            // Scanned locks must be counted even if the scan stops early.
+            assert_eq!(reader.statistics.lock.processed_keys, expect_res.len());
         };
 
         check_scan_lock(None, None, 6, &visible_locks, false);
@@ -160,6 +160,12 @@ impl<S: Snapshot> ScannerBuilder<S> {
         from_ts: TimeStamp,
         extra_op: ExtraOp,
     ) -> Result<DeltaScanner<S>> {
+        if extra_op == ExtraOp::ReadOldValue {
+            // Reading the old value needs to seek writes committed before `from_ts`
+            // with the same write cursor, so SSTs containing only older versions
+            // must not be filtered out.
+            self.0.hint_min_ts = None;
+        }
         let lock_cursor = self.0.create_cf_cursor(CF_LOCK)?;
         let write_cursor = self.0.create_cf_cursor(CF_WRITE)?;
         // Note: Create a default cf cursor will take key range, so we need to
@@ -64,9 +64,11 @@ impl<'a, S: Snapshot> RawStore<S> {
         end_key: Option<&'a Key>,
         limit: usize,
         statistics: &'a mut Statistics,
+        fill_cache: bool,
         key_only: bool,
     ) -> Result<Vec<Result<KvPair>>> {
         let mut option = IterOptions::default();
+        option.set_fill_cache(fill_cache);
         if let Some(end) = end_key {
             option.set_upper_bound(end.as_encoded(), DATA_KEY_PREFIX_LEN);
         }
@@ -94,9 +96,11 @@ impl<'a, S: Snapshot> RawStore<S> {
         end_key: Option<&'a Key>,
         limit: usize,
         statistics: &'a mut Statistics,
+        fill_cache: bool,
         key_only: bool,
     ) -> Result<Vec<Result<KvPair>>> {
         let mut option = IterOptions::default();
+        option.set_fill_cache(fill_cache);
         if let Some(end) = end_key {
             option.set_lower_bound(end.as_encoded(), DATA_KEY_PREFIX_LEN);
         }
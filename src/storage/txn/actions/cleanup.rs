@@ -53,6 +53,7 @@ pub fn cleanup<S: Snapshot>(
             l,
             MissingLockAction::rollback_protect(protect_rollback),
             false,
+            None,
         )? {
             TxnStatus::Committed { commit_ts } => {
                 MVCC_CONFLICT_COUNTER.rollback_committed.inc();
@@ -1,7 +1,7 @@
 // Copyright 2020 TiKV Project Authors. Licensed under Apache-2.0.
 
 use crate::storage::mvcc::{
-    metrics::{MVCC_CONFLICT_COUNTER, MVCC_DUPLICATE_CMD_COUNTER_VEC},
+    metrics::{MVCC_CONFLICT_COUNTER, MVCC_DUPLICATE_CMD_COUNTER_VEC, MVCC_TXN_MODE_COUNTER_VEC},
     ErrorInner, LockType, MvccTxn, ReleasedLock, Result as MvccResult, SnapshotReader,
 };
 use crate::storage::Snapshot;
@@ -83,6 +83,16 @@ pub fn commit<S: Snapshot>(
             };
         }
     };
+
+    if lock.is_pessimistic_txn() {
+        MVCC_TXN_MODE_COUNTER_VEC.commit.pessimistic.inc();
+    } else {
+        MVCC_TXN_MODE_COUNTER_VEC.commit.optimistic.inc();
+    }
+    if lock.use_async_commit {
+        MVCC_TXN_MODE_COUNTER_VEC.commit.async_commit.inc();
+    }
+
     let mut write = Write::new(
         WriteType::from_lock_type(lock.lock_type).unwrap(),
         reader.start_ts,
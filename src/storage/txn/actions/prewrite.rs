@@ -1,9 +1,10 @@
 // Copyright 2020 TiKV Project Authors. Licensed under Apache-2.0.
 use crate::storage::{
+    hot_keys,
     mvcc::{
         metrics::{
             CONCURRENCY_MANAGER_LOCK_DURATION_HISTOGRAM, MVCC_CONFLICT_COUNTER,
-            MVCC_DUPLICATE_CMD_COUNTER_VEC,
+            MVCC_DUPLICATE_CMD_COUNTER_VEC, MVCC_TXN_MODE_COUNTER_VEC,
         },
         Error, ErrorInner, Lock, LockType, MvccTxn, Result, SnapshotReader, TxnCommitRecord,
     },
@@ -42,6 +43,17 @@ pub fn prewrite<S: Snapshot>(
         .into())
     );
 
+    match txn_props.kind {
+        TransactionKind::Optimistic(_) => MVCC_TXN_MODE_COUNTER_VEC.prewrite.optimistic.inc(),
+        TransactionKind::Pessimistic(_) => MVCC_TXN_MODE_COUNTER_VEC.prewrite.pessimistic.inc(),
+    }
+    match txn_props.commit_kind {
+        CommitKind::TwoPc => (),
+        CommitKind::Async(_) => MVCC_TXN_MODE_COUNTER_VEC.prewrite.async_commit.inc(),
+        CommitKind::OnePc(_) => MVCC_TXN_MODE_COUNTER_VEC.prewrite.one_pc.inc(),
+    }
+    hot_keys::maybe_sample(mutation.key.as_encoded());
+
     let lock_status = match reader.load_lock(&mutation.key)? {
         Some(lock) => mutation.check_lock(lock, is_pessimistic_lock)?,
         None if is_pessimistic_lock => {
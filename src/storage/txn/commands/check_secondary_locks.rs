@@ -194,6 +194,7 @@ pub mod tests {
                     extra_op: Default::default(),
                     statistics: &mut Default::default(),
                     async_apply_prewrite: false,
+                    txn_status_cache: None,
                 },
             )
             .unwrap();
@@ -229,6 +230,7 @@ pub mod tests {
                         extra_op: Default::default(),
                         statistics: &mut Default::default(),
                         async_apply_prewrite: false,
+                        txn_status_cache: None,
                     },
                 )
                 .unwrap();
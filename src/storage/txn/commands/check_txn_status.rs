@@ -107,6 +107,7 @@ impl<S: Snapshot, L: LockManager> WriteCommand<S, L> for CheckTxnStatus {
                     l,
                     MissingLockAction::rollback(self.rollback_if_not_exist),
                     self.resolving_pessimistic_lock,
+                    context.txn_status_cache,
                 )?,
                 None,
             ),
@@ -143,6 +144,7 @@ pub mod tests {
     use crate::storage::mvcc::tests::*;
     use crate::storage::txn::commands::{pessimistic_rollback, WriteCommand, WriteContext};
     use crate::storage::txn::tests::*;
+    use crate::storage::txn::txn_status_cache::TxnStatusCache;
     use crate::storage::{types::TxnStatus, ProcessResult, TestEngineBuilder};
     use concurrency_manager::ConcurrencyManager;
     use kvproto::kvrpcpb::Context;
@@ -184,6 +186,7 @@ pub mod tests {
                     extra_op: Default::default(),
                     statistics: &mut Default::default(),
                     async_apply_prewrite: false,
+                    txn_status_cache: None,
                 },
             )
             .unwrap();
@@ -230,6 +233,7 @@ pub mod tests {
                         extra_op: Default::default(),
                         statistics: &mut Default::default(),
                         async_apply_prewrite: false,
+                        txn_status_cache: None,
                     },
                 )
                 .is_err()
@@ -972,6 +976,69 @@ pub mod tests {
         test_check_txn_status_impl(true);
     }
 
+    #[test]
+    fn test_check_txn_status_from_cache() {
+        let engine = TestEngineBuilder::new().build().unwrap();
+        let (k, v) = (b"k1", b"v1");
+
+        let run = |cache: &TxnStatusCache, lock_ts: u64| {
+            let snapshot = engine.snapshot(Default::default()).unwrap();
+            let cm = ConcurrencyManager::new(30.into());
+            let command = crate::storage::txn::commands::CheckTxnStatus {
+                ctx: Context::default(),
+                primary_key: Key::from_raw(k),
+                lock_ts: lock_ts.into(),
+                caller_start_ts: 25.into(),
+                current_ts: 25.into(),
+                rollback_if_not_exist: true,
+                force_sync_commit: false,
+                resolving_pessimistic_lock: false,
+            };
+            let result = command
+                .process_write(
+                    snapshot,
+                    WriteContext {
+                        lock_mgr: &DummyLockManager,
+                        concurrency_manager: cm,
+                        extra_op: Default::default(),
+                        statistics: &mut Default::default(),
+                        async_apply_prewrite: false,
+                        txn_status_cache: Some(cache),
+                    },
+                )
+                .unwrap();
+            if let ProcessResult::TxnStatus { txn_status } = result.pr {
+                txn_status
+            } else {
+                unreachable!();
+            }
+        };
+
+        must_prewrite_put(&engine, k, v, k, 10);
+        must_commit(&engine, k, 10, 20);
+
+        // The first call reads the commit record from the write CF and fills
+        // the cache.
+        let cache = TxnStatusCache::default();
+        assert_eq!(
+            run(&cache, 10),
+            TxnStatus::Committed {
+                commit_ts: 20.into()
+            }
+        );
+        assert_eq!(cache.get_committed(10.into()), Some(20.into()));
+
+        // A cached transaction is answered without reading the write CF: this
+        // one has no record in the engine at all.
+        cache.insert_committed(11.into(), 21.into());
+        assert_eq!(
+            run(&cache, 11),
+            TxnStatus::Committed {
+                commit_ts: 21.into()
+            }
+        );
+    }
+
     #[test]
     fn test_check_txn_status_resolving_pessimistic_lock() {
         let engine = TestEngineBuilder::new().build().unwrap();
@@ -149,6 +149,7 @@ mod tests {
             extra_op: ExtraOp::Noop,
             statistics: &mut statistic,
             async_apply_prewrite: false,
+            txn_status_cache: None,
         };
         let ret = cmd.cmd.process_write(snap, context)?;
         match ret.pr {
@@ -53,6 +53,7 @@ use crate::storage::kv::WriteData;
 use crate::storage::lock_manager::{self, LockManager, WaitTimeout};
 use crate::storage::mvcc::{Lock as MvccLock, MvccReader, ReleasedLock, SnapshotReader};
 use crate::storage::txn::latch;
+use crate::storage::txn::txn_status_cache::TxnStatusCache;
 use crate::storage::txn::{ProcessResult, Result};
 use crate::storage::types::{
     MvccInfo, PessimisticLockRes, PrewriteResult, SecondaryLocksStatus, StorageCallbackType,
@@ -357,6 +358,14 @@ pub enum ResponsePolicy {
     /// Return the response after finishing Raft committing.
     OnCommitted,
     /// Return the response after finishing raft proposing.
+    ///
+    /// Only commands whose effect may be lost without breaking safety can use
+    /// this: a pipelined pessimistic lock that fails to apply is the same as
+    /// never having locked, and the client falls back to retrying. Commands
+    /// whose response the client acts on, like a heartbeat extending a TTL,
+    /// must keep `OnApplied` — reporting success for an update that is then
+    /// lost would leave the client with wrong knowledge. If the propose
+    /// itself fails, the error is returned through the ordinary callback.
     OnProposed,
 }
 
@@ -494,6 +503,9 @@ pub struct WriteContext<'a, L: LockManager> {
     pub extra_op: ExtraOp,
     pub statistics: &'a mut Statistics,
     pub async_apply_prewrite: bool,
+    /// The scheduler's cache of commit records. `None` when the command is
+    /// executed out of the scheduler, e.g. in tests.
+    pub txn_status_cache: Option<&'a TxnStatusCache>,
 }
 
 pub struct ReaderWithStats<'a, S: Snapshot> {
@@ -709,6 +721,7 @@ pub mod test_util {
             extra_op: ExtraOp::Noop,
             statistics,
             async_apply_prewrite: false,
+            txn_status_cache: None,
         };
         let ret = cmd.cmd.process_write(snap, context)?;
         let res = match ret.pr {
@@ -846,6 +859,7 @@ pub mod test_util {
             extra_op: ExtraOp::Noop,
             statistics,
             async_apply_prewrite: false,
+            txn_status_cache: None,
         };
 
         let ret = cmd.cmd.process_write(snap, context)?;
@@ -870,6 +884,7 @@ pub mod test_util {
             extra_op: ExtraOp::Noop,
             statistics,
             async_apply_prewrite: false,
+            txn_status_cache: None,
         };
 
         let ret = cmd.cmd.process_write(snap, context)?;
@@ -111,6 +111,15 @@ pub mod tests {
         key: &[u8],
         start_ts: impl Into<TimeStamp>,
         for_update_ts: impl Into<TimeStamp>,
+    ) {
+        must_success_keys(engine, &[key], start_ts, for_update_ts)
+    }
+
+    pub fn must_success_keys<E: Engine>(
+        engine: &E,
+        keys: &[&[u8]],
+        start_ts: impl Into<TimeStamp>,
+        for_update_ts: impl Into<TimeStamp>,
     ) {
         let ctx = Context::default();
         let snapshot = engine.snapshot(Default::default()).unwrap();
@@ -119,7 +128,7 @@ pub mod tests {
         let start_ts = start_ts.into();
         let command = crate::storage::txn::commands::PessimisticRollback {
             ctx: ctx.clone(),
-            keys: vec![Key::from_raw(key)],
+            keys: keys.iter().map(|k| Key::from_raw(k)).collect(),
             start_ts,
             for_update_ts,
         };
@@ -130,6 +139,7 @@ pub mod tests {
             extra_op: Default::default(),
             statistics: &mut Default::default(),
             async_apply_prewrite: false,
+            txn_status_cache: None,
         };
         let result = command.process_write(snapshot, write_context).unwrap();
         write(engine, &ctx, result.to_be_write.modifies);
@@ -193,4 +203,30 @@ pub mod tests {
         must_success(&engine, k, 3, 4);
         must_success(&engine, k, 3, 5);
     }
+
+    #[test]
+    fn test_pessimistic_rollback_multiple_keys() {
+        let engine = TestEngineBuilder::new().build().unwrap();
+
+        let keys: &[&[u8]] = &[b"k1", b"k2", b"k3"];
+        for k in keys {
+            must_acquire_pessimistic_lock(&engine, k, b"k1", 10, 10);
+            must_pessimistic_locked(&engine, k, 10, 10);
+        }
+        // Another transaction's lock must survive the batch rollback.
+        must_acquire_pessimistic_lock(&engine, b"k4", b"k4", 11, 11);
+
+        must_success_keys(&engine, &[b"k1", b"k2", b"k3", b"k4"], 10, 10);
+        for k in keys {
+            must_unlocked(&engine, k);
+            must_get_commit_ts_none(&engine, k, 10);
+        }
+        must_pessimistic_locked(&engine, b"k4", 11, 11);
+
+        // Rolling back the same keys again is idempotent.
+        must_success_keys(&engine, &[b"k1", b"k2", b"k3"], 10, 10);
+        for k in keys {
+            must_unlocked(&engine, k);
+        }
+    }
 }
@@ -130,6 +130,7 @@ pub mod tests {
                     extra_op: Default::default(),
                     statistics: &mut Default::default(),
                     async_apply_prewrite: false,
+                    txn_status_cache: None,
                 },
             )
             .unwrap();
@@ -170,6 +171,7 @@ pub mod tests {
                         extra_op: Default::default(),
                         statistics: &mut Default::default(),
                         async_apply_prewrite: false,
+                        txn_status_cache: None,
                     },
                 )
                 .is_err()
@@ -10,6 +10,7 @@ pub mod scheduler;
 mod actions;
 mod latch;
 mod store;
+mod txn_status_cache;
 
 use std::error::Error as StdError;
 use std::io::Error as IoError;
@@ -40,6 +41,7 @@ pub use self::store::{
     EntryBatch, FixtureStore, FixtureStoreScanner, Scanner, SnapshotStore, Store, TxnEntry,
     TxnEntryScanner, TxnEntryStore,
 };
+pub use self::txn_status_cache::TxnStatusCache;
 
 /// Process result of a command.
 #[derive(Debug)]
@@ -56,6 +56,7 @@ use crate::storage::txn::{
     flow_controller::FlowController,
     latch::{Latches, Lock},
     sched_pool::{tls_collect_read_duration, tls_collect_scan_details, SchedPool},
+    txn_status_cache::TxnStatusCache,
     Error, ProcessResult,
 };
 use crate::storage::{
@@ -203,6 +204,8 @@ struct SchedulerInner<L: LockManager> {
     pipelined_pessimistic_lock: Arc<AtomicBool>,
 
     enable_async_apply_prewrite: bool,
+
+    txn_status_cache: TxnStatusCache,
 }
 
 #[inline]
@@ -343,6 +346,7 @@ impl<E: Engine, L: LockManager> Scheduler<E, L> {
             pipelined_pessimistic_lock,
             enable_async_apply_prewrite: config.enable_async_apply_prewrite,
             flow_controller,
+            txn_status_cache: TxnStatusCache::default(),
         });
 
         slow_log!(
@@ -722,7 +726,9 @@ impl<E: Engine, L: LockManager> Scheduler<E, L> {
                         Command::Commit(_) => {
                             tls_collect_query(region_id, QueryKind::Commit);
                         }
-                        Command::Rollback(_) | Command::PessimisticRollback(_) => {
+                        Command::Cleanup(_)
+                        | Command::Rollback(_)
+                        | Command::PessimisticRollback(_) => {
                             tls_collect_query(region_id, QueryKind::Rollback);
                         }
                         _ => {}
@@ -788,6 +794,7 @@ impl<E: Engine, L: LockManager> Scheduler<E, L> {
                 extra_op: task.extra_op,
                 statistics,
                 async_apply_prewrite: self.inner.enable_async_apply_prewrite,
+                txn_status_cache: Some(&self.inner.txn_status_cache),
             };
 
             task.cmd
@@ -0,0 +1,69 @@
+// Copyright 2021 TiKV Project Authors. Licensed under Apache-2.0.
+
+//! A store level cache of the commit records of recently checked transactions.
+//!
+//! When a large transaction commits, every reader blocked by one of its locks
+//! calls `CheckTxnStatus` on the primary key, and each call seeks the write CF
+//! for the commit record. The cache remembers `start_ts -> commit_ts` once the
+//! record has been read so that the following calls are answered from memory.
+//!
+//! Only committed transactions are cached. A commit record read from the
+//! write CF is a durable and immutable fact, so entries never need to be
+//! invalidated, not even on region leader change. Rollbacks are not cached
+//! because collapsible rollback records can be removed from the write CF.
+
+use std::sync::Mutex;
+
+use tikv_util::lru::LruCache;
+use txn_types::TimeStamp;
+
+/// The number of transactions tracked by the cache. An entry is two
+/// timestamps, so the memory usage is negligible.
+const CAPACITY: usize = 4096;
+
+pub struct TxnStatusCache {
+    committed: Mutex<LruCache<TimeStamp, TimeStamp>>,
+}
+
+impl Default for TxnStatusCache {
+    fn default() -> TxnStatusCache {
+        TxnStatusCache {
+            committed: Mutex::new(LruCache::with_capacity(CAPACITY)),
+        }
+    }
+}
+
+impl TxnStatusCache {
+    /// Records that the transaction starting at `start_ts` is committed at
+    /// `commit_ts`. It must only be called with facts read from the write CF.
+    pub fn insert_committed(&self, start_ts: TimeStamp, commit_ts: TimeStamp) {
+        if let Ok(mut committed) = self.committed.try_lock() {
+            committed.insert(start_ts, commit_ts);
+        }
+    }
+
+    /// Returns the commit_ts of the transaction starting at `start_ts` if it
+    /// is known to be committed. `None` only means the cache doesn't know.
+    pub fn get_committed(&self, start_ts: TimeStamp) -> Option<TimeStamp> {
+        // Contention is skipped instead of waited for; the caller falls back
+        // to reading the write CF.
+        self.committed
+            .try_lock()
+            .ok()
+            .and_then(|mut committed| committed.get(&start_ts).copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_txn_status_cache() {
+        let cache = TxnStatusCache::default();
+        assert_eq!(cache.get_committed(100.into()), None);
+        cache.insert_committed(100.into(), 110.into());
+        assert_eq!(cache.get_committed(100.into()), Some(110.into()));
+        assert_eq!(cache.get_committed(101.into()), None);
+    }
+}